@@ -0,0 +1,84 @@
+use std::{
+    error::Error,
+    fs::{self, File},
+    io::BufReader,
+    path::PathBuf,
+    time::{Duration, SystemTime},
+};
+
+use serde::{Deserialize, Serialize};
+
+use crate::frontend::ComponentKind;
+
+/// The cached articles older than this are removed
+const MAX_AGE: Duration = Duration::from_secs(30 * 24 * 60 * 60);
+/// Only the newest articles are kept
+const MAX_ARTICLES: usize = 5000;
+
+#[derive(Serialize, Deserialize)]
+struct CachedArticle {
+    url: String,
+    content: Vec<ComponentKind>,
+}
+
+fn dir() -> Option<PathBuf> {
+    Some(dirs::cache_dir()?.join("newsminal").join("articles"))
+}
+
+/// FNV-1a, because the file names have to stay the same between builds
+fn hash(url: &str) -> u64 {
+    url.bytes().fold(0xcbf29ce484222325, |hash, byte| {
+        (hash ^ byte as u64).wrapping_mul(0x100000001b3)
+    })
+}
+
+fn path(url: &str) -> Option<PathBuf> {
+    Some(dir()?.join(format!("{:016x}.json", hash(url))))
+}
+
+pub fn contains(url: &str) -> bool {
+    path(url).is_some_and(|path| path.exists())
+}
+
+pub fn load(url: &str) -> Option<Vec<ComponentKind>> {
+    let file = File::open(path(url)?).ok()?;
+    let article = serde_json::from_reader::<_, CachedArticle>(BufReader::new(file))
+        .inspect_err(|err| log::error!("Couldn't read cached article {url}: {err}"))
+        .ok()?;
+    (article.url == url).then_some(article.content)
+}
+
+pub fn store(url: &str, content: &[ComponentKind]) -> Result<(), Box<dyn Error>> {
+    let path = path(url).ok_or("Couldn't find the cache directory")?;
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    let article = CachedArticle {
+        url: url.into(),
+        content: content.to_vec(),
+    };
+    fs::write(path, serde_json::to_vec(&article)?)?;
+    Ok(())
+}
+
+/// Removes the articles older than MAX_AGE and the oldest ones over MAX_ARTICLES
+pub fn prune() -> Result<(), Box<dyn Error>> {
+    let Some(dir) = dir().filter(|dir| dir.exists()) else {
+        return Ok(());
+    };
+    let mut articles = fs::read_dir(dir)?
+        .filter_map(|entry| {
+            let entry = entry.ok()?;
+            Some((entry.metadata().ok()?.modified().ok()?, entry.path()))
+        })
+        .collect::<Vec<_>>();
+    articles.sort_by_key(|(modified, _)| std::cmp::Reverse(*modified));
+    let now = SystemTime::now();
+    for (index, (modified, path)) in articles.iter().enumerate() {
+        let age = now.duration_since(*modified).unwrap_or_default();
+        if index >= MAX_ARTICLES || age > MAX_AGE {
+            fs::remove_file(path)?;
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,132 @@
+use std::collections::HashSet;
+
+use chrono::TimeDelta;
+
+use crate::{Feed, FeedItem, frontend::fold};
+
+/// Only the headlines published this close together can be about the same story
+const WINDOW: TimeDelta = TimeDelta::hours(12);
+/// How similar (by the Dice coefficient of their words) two headlines have to be
+const MIN_SIMILARITY: f64 = 0.5;
+/// Sharing a single word (like a name) isn't enough for short headlines
+const MIN_COMMON_WORDS: usize = 2;
+/// Shorter words (prepositions, conjunctions...) don't say much about the story
+const MIN_WORD_LEN: usize = 4;
+/// Words are compared by their beginning, so that the different cases of a word match
+const STEM_LEN: usize = 6;
+
+/// The stems of the words of the headline, without the source badge
+fn headline_words(item: &FeedItem) -> HashSet<String> {
+    let title = match item.title.split_once("] ") {
+        Some((badge, title)) if badge.starts_with('[') => title,
+        _ => &item.title,
+    };
+    title
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|word| word.chars().count() >= MIN_WORD_LEN)
+        .map(|word| word.chars().flat_map(fold).take(STEM_LEN).collect())
+        .collect()
+}
+
+fn similarity(first: &HashSet<String>, second: &HashSet<String>) -> f64 {
+    if first.is_empty() || second.is_empty() {
+        return 0.0;
+    }
+    let common = first.intersection(second).count();
+    if common < MIN_COMMON_WORDS {
+        return 0.0;
+    }
+    2.0 * common as f64 / (first.len() + second.len()) as f64
+}
+
+impl Feed {
+    /// Groups the items about the same story from different sources. Every item gets the index
+    /// of the newest item of its group.
+    pub(super) fn update_clusters(&mut self) {
+        let words = self.items.iter().map(headline_words).collect::<Vec<_>>();
+        self.clusters = (0..self.items.len()).collect();
+        for index in 0..self.items.len() {
+            let item = &self.items[index];
+            let similar = (0..index).find(|&other| {
+                let cluster = self.clusters[other];
+                let other_item = &self.items[other];
+                (other_item.published - item.published).abs() <= WINDOW
+                    && similarity(&words[index], &words[other]) >= MIN_SIMILARITY
+                    // One version of the story per source
+                    && !(cluster..index).any(|member| {
+                        self.clusters[member] == cluster
+                            && self.items[member].parser.to_string() == item.parser.to_string()
+                    })
+            });
+            if let Some(other) = similar {
+                self.clusters[index] = self.clusters[other];
+            }
+        }
+    }
+
+    fn cluster_members(&self, cluster: usize) -> impl Iterator<Item = usize> {
+        (cluster..self.items.len()).filter(move |&index| self.clusters[index] == cluster)
+    }
+
+    fn is_expanded(&self, cluster: usize) -> bool {
+        self.expanded.contains(self.items[cluster].id())
+    }
+
+    /// The indexes of the items that pass the filter. Only the first item of a story is shown,
+    /// unless the story is expanded.
+    pub(super) fn compute_shown(&self, filter: &super::Filter) -> Vec<usize> {
+        let mut seen = HashSet::new();
+        (0..self.items.len())
+            .filter(|&index| filter.matches(&self.items[index]))
+            .filter(|&index| {
+                let cluster = self.clusters[index];
+                seen.insert(cluster) || self.is_expanded(cluster)
+            })
+            .collect()
+    }
+
+    /// The sources of the story, if the item is the first shown one of a story with more than one
+    /// version, and whether the story is expanded
+    pub fn cluster_sources(&self, index: usize) -> Option<(Vec<String>, bool)> {
+        let cluster = self.clusters[index];
+        let first_shown = self
+            .shown
+            .iter()
+            .copied()
+            .find(|&shown| self.clusters[shown] == cluster)?;
+        if first_shown != index {
+            return None;
+        }
+        let sources = self
+            .cluster_members(cluster)
+            .map(|member| self.items[member].parser.to_string())
+            .collect::<Vec<_>>();
+        (sources.len() > 1).then(|| (sources, self.is_expanded(cluster)))
+    }
+
+    /// Whether the item is shown only because its story is expanded
+    pub fn is_expanded_version(&self, index: usize) -> bool {
+        let cluster = self.clusters[index];
+        self.is_expanded(cluster) && self.cluster_sources(index).is_none()
+    }
+
+    /// Expands or collapses the story of the selected item. The first shown version of the story
+    /// gets selected.
+    pub fn toggle_selected_cluster(&mut self) -> bool {
+        let cluster = self.clusters[self.shown[self.selected]];
+        if self.cluster_members(cluster).count() < 2 {
+            return false;
+        }
+        let id = self.items[cluster].id().to_string();
+        if !self.expanded.remove(&id) {
+            self.expanded.insert(id);
+        }
+        self.shown = self.compute_shown(&self.filter);
+        self.selected = self
+            .shown
+            .iter()
+            .position(|&index| self.clusters[index] == cluster)
+            .unwrap_or(0);
+        true
+    }
+}
@@ -0,0 +1,146 @@
+use std::{error::Error, fmt::Display, sync::Arc};
+
+use scraper::{ElementRef, Html};
+
+use crate::{Body, FeedItem, config::SourceConfig, frontend::ComponentKind};
+
+use super::{BackendError, Client, NewsSite, Parser, formats::Entry, generic};
+
+/// A RSS, Atom or JSON feed added by the user in the config file
+#[derive(Clone)]
+pub struct Custom {
+    name: String,
+    url: String,
+}
+
+impl From<&SourceConfig> for Custom {
+    fn from(value: &SourceConfig) -> Self {
+        Self {
+            name: value.name.clone(),
+            url: value.url.clone(),
+        }
+    }
+}
+
+impl Custom {
+    /// A source that isn't in the config anymore, so only its name is known
+    pub fn without_feed(name: String) -> Self {
+        Self {
+            name,
+            url: String::new(),
+        }
+    }
+}
+
+impl Display for Custom {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.name)
+    }
+}
+
+impl NewsSite for Custom {
+    fn get_feed_items(&self, client: &Client) -> Result<Vec<FeedItem>, Box<dyn Error>> {
+        super::parsers::get_feed_items_with(client, Arc::new(self.clone()), &self.url, body)
+    }
+}
+
+/// There is no parser for the article page, so the body is what the feed gives us
+fn body(entry: &Entry) -> Option<Body> {
+    let body = match (&entry.content, &entry.description) {
+        (Some(html), lead) => Body::Fetched {
+            html: html.clone(),
+            lead: lead.as_deref().map(strip_html).unwrap_or_default(),
+        },
+        (None, Some(html)) => Body::Fetched {
+            html: html.clone(),
+            lead: String::new(),
+        },
+        (None, None) => Body::ToFetch {
+            url: entry.link.clone()?,
+        },
+    };
+    Some(body)
+}
+
+fn strip_html(html: &str) -> String {
+    Html::parse_fragment(html)
+        .root_element()
+        .text()
+        .collect::<String>()
+        .trim()
+        .into()
+}
+
+impl Parser for Custom {
+    fn parse_article_content(&self, elem: ElementRef) -> Option<ComponentKind> {
+        let text: String = elem.text().collect::<String>().trim().into();
+        if text.is_empty() {
+            return None;
+        }
+        match elem.value().name() {
+            "p" | "div" | "li" => Some(ComponentKind::Paragraph(text)),
+            "h1" | "h2" | "h3" | "h4" => Some(ComponentKind::Subtitle(text)),
+            "blockquote" => Some(ComponentKind::Boxed(vec![text])),
+            _ => None,
+        }
+    }
+
+    fn parse_article(&self, html: Html) -> Result<Vec<ComponentKind>, BackendError> {
+        let root = html.root_element();
+        // Whole pages (items without a description) are read like the articles of any other site
+        if root
+            .child_elements()
+            .any(|elem| elem.value().name() == "body")
+        {
+            return generic::extract(html);
+        }
+        let mut body = root
+            .child_elements()
+            .filter_map(|elem| self.parse_article_content(elem))
+            .collect::<Vec<_>>();
+        if body.is_empty() {
+            // Descriptions are often just text without any tags
+            let text: String = root.text().collect::<String>().trim().into();
+            if !text.is_empty() {
+                body.push(ComponentKind::Paragraph(text));
+            }
+        }
+        if body.is_empty() {
+            return Err(BackendError::NoContent);
+        }
+        Ok(body)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::backend::formats::read_entries;
+
+    #[test]
+    fn shows_the_content_from_the_feed() {
+        let atom = r#"<feed xmlns="http://www.w3.org/2005/Atom">
+            <title>Vesti</title><id>urn:vesti</id><updated>2026-10-17T12:00:00Z</updated>
+            <entry>
+                <title>Sa tekstom</title><id>urn:1</id><updated>2026-10-17T11:00:00Z</updated>
+                <link href="https://vesti.rs/1"/>
+                <summary type="html">&lt;b&gt;Uvod&lt;/b&gt;</summary>
+                <content type="html">&lt;p&gt;Ceo tekst&lt;/p&gt;</content>
+            </entry>
+            <entry>
+                <title>Samo link</title><id>urn:2</id><updated>2026-10-17T10:00:00Z</updated>
+                <link href="https://vesti.rs/2"/>
+            </entry>
+        </feed>"#;
+        let entries = read_entries(atom.as_bytes()).unwrap();
+        let bodies = entries.iter().map(body).collect::<Vec<_>>();
+        assert!(matches!(
+            &bodies[0],
+            Some(Body::Fetched { html, lead }) if html == "<p>Ceo tekst</p>" && lead == "Uvod"
+        ));
+        assert!(matches!(
+            &bodies[1],
+            Some(Body::ToFetch { url }) if url == "https://vesti.rs/2"
+        ));
+    }
+}
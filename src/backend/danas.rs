@@ -1,11 +1,10 @@
-use std::{error::Error, fmt::Display, rc::Rc};
+use std::{error::Error, fmt::Display, sync::Arc};
 
-use reqwest::blocking::Client;
 use scraper::Html;
 
 use crate::{FeedItem, frontend::ComponentKind};
 
-use super::{BackendError, NewsSite, Parser};
+use super::{BackendError, Client, NewsSite, Parser};
 
 pub struct Danas;
 
@@ -17,7 +16,7 @@ impl Display for Danas {
 
 impl NewsSite for Danas {
     fn get_feed_items(&self, client: &Client) -> Result<Vec<FeedItem>, Box<dyn Error>> {
-        super::parsers::get_feed_items(client, Rc::new(Self), "https://danas.rs/feed")
+        super::parsers::get_feed_items(client, Arc::new(Self), "https://danas.rs/feed")
     }
 }
 
@@ -60,6 +59,6 @@ impl Parser for Danas {
     // FIXME: BBC articles don't work
     //        (CONTENT_SELECTOR should be ".content div.flex .w-full div")
     fn parse_article(&self, html: Html) -> Result<Vec<ComponentKind>, BackendError> {
-        super::parsers::parse_article(Rc::new(Self), html, ".content div.flex .w-full")
+        super::parsers::parse_article(Arc::new(Self), html, ".content div.flex .w-full")
     }
 }
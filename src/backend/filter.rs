@@ -0,0 +1,103 @@
+use std::collections::HashSet;
+
+use crate::{Feed, FeedItem};
+
+use super::{Refreshed, Registry, Topic};
+
+/// Which feed items are shown in the feed view
+#[derive(Default, Clone)]
+pub struct Filter {
+    pub unread_only: bool,
+    /// Names of the news sites whose items aren't shown
+    pub hidden_sources: HashSet<String>,
+    pub topic: Option<Topic>,
+}
+
+impl Filter {
+    /// The items of the sources that aren't enabled stay in the feed (and in the saved feed), but
+    /// they are never shown
+    pub fn matches(&self, item: &FeedItem) -> bool {
+        let source = item.parser.to_string();
+        if !Registry::get().is_enabled(&source) {
+            return false;
+        }
+        if self.unread_only && item.read {
+            return false;
+        }
+        if let Some(topic) = self.topic
+            && !item.has_topic(topic)
+        {
+            return false;
+        }
+        !self.hidden_sources.contains(&source)
+    }
+
+    pub fn is_active(&self) -> bool {
+        self.unread_only || !self.hidden_sources.is_empty() || self.topic.is_some()
+    }
+
+    pub fn toggle_source(&mut self, name: String) {
+        if !self.hidden_sources.remove(&name) {
+            self.hidden_sources.insert(name);
+        }
+    }
+}
+
+impl Feed {
+    pub fn filter(&self) -> &Filter {
+        &self.filter
+    }
+
+    pub fn shown(&self) -> impl DoubleEndedIterator<Item = &FeedItem> + ExactSizeIterator {
+        self.shown.iter().map(|&index| &self.items[index])
+    }
+
+    pub fn num_shown(&self) -> usize {
+        self.shown.len()
+    }
+
+    /// Changes the filter, unless nothing would be shown with it. The selected item stays selected
+    /// if it is still shown, otherwise the next shown one gets selected.
+    pub fn set_filter(&mut self, filter: Filter) -> bool {
+        let shown = self.compute_shown(&filter);
+        if shown.is_empty() {
+            return false;
+        }
+        let selected_item = self.shown.get(self.selected).copied().unwrap_or(0);
+        self.selected = shown
+            .partition_point(|&index| index < selected_item)
+            .min(shown.len() - 1);
+        self.shown = shown;
+        self.filter = filter;
+        true
+    }
+
+    /// Updates the shown items after num_new items were pushed to the front of the feed
+    pub(super) fn shift_shown(&mut self, num_new: usize) -> Refreshed {
+        self.update_clusters();
+        let selected_item = self
+            .shown
+            .get(self.selected)
+            .map_or(0, |index| index + num_new);
+        let old_shown = self
+            .shown
+            .iter()
+            .map(|index| index + num_new)
+            .collect::<Vec<_>>();
+        self.shown = self.compute_shown(&self.filter);
+        let num_new_shown = self.shown.len().saturating_sub(old_shown.len());
+        if self.shown[num_new_shown..] == old_shown
+            && self.shown[..num_new_shown]
+                .iter()
+                .all(|&index| index < num_new)
+        {
+            return Refreshed::Added(num_new_shown);
+        }
+        // The new items joined stories that were already shown
+        self.selected = self
+            .shown
+            .partition_point(|&index| index < selected_item)
+            .min(self.shown.len().saturating_sub(1));
+        Refreshed::Regrouped
+    }
+}
@@ -0,0 +1,258 @@
+use std::error::Error;
+
+use chrono::{DateTime, Local};
+use rss::Channel;
+use serde::Deserialize;
+
+/// A feed entry normalized from any of the supported feed formats
+pub struct Entry {
+    pub title: String,
+    pub published: DateTime<Local>,
+    pub link: Option<String>,
+    pub description: Option<String>,
+    pub content: Option<String>,
+    pub categories: Vec<String>,
+}
+
+enum Format {
+    Rss,
+    Atom,
+    Json,
+}
+
+impl Format {
+    fn detect(feed: &[u8]) -> Self {
+        let start = feed
+            .iter()
+            .position(|b| !b.is_ascii_whitespace() && !matches!(b, 0xEF | 0xBB | 0xBF))
+            .unwrap_or(0);
+        if feed.get(start) == Some(&b'{') {
+            return Self::Json;
+        }
+        // The root element comes after the xml declaration, comments and stylesheets
+        let head = String::from_utf8_lossy(&feed[start..feed.len().min(start + 1024)]);
+        match (head.find("<feed"), head.find("<rss")) {
+            (Some(_), None) => Self::Atom,
+            (Some(atom), Some(rss)) if atom < rss => Self::Atom,
+            _ => Self::Rss,
+        }
+    }
+}
+
+pub fn read_entries(feed: &[u8]) -> Result<Vec<Entry>, Box<dyn Error>> {
+    // The xml readers don't expect a byte order mark
+    let feed = feed.strip_prefix(b"\xEF\xBB\xBF").unwrap_or(feed);
+    match Format::detect(feed) {
+        Format::Rss => read_rss(feed),
+        Format::Atom => read_atom(feed),
+        Format::Json => read_json(feed),
+    }
+}
+
+fn read_rss(feed: &[u8]) -> Result<Vec<Entry>, Box<dyn Error>> {
+    let channel = Channel::read_from(feed)?;
+    Ok(channel
+        .items
+        .iter()
+        .filter_map(|item| {
+            Some(Entry {
+                title: item.title()?.into(),
+                published: DateTime::parse_from_rfc2822(item.pub_date()?).ok()?.into(),
+                link: item.link().map(String::from),
+                description: item.description().map(String::from),
+                content: item.content().map(String::from),
+                categories: item
+                    .categories()
+                    .iter()
+                    .map(|category| category.name().trim().into())
+                    .collect(),
+            })
+        })
+        .collect())
+}
+
+fn read_atom(feed: &[u8]) -> Result<Vec<Entry>, Box<dyn Error>> {
+    let feed = atom_syndication::Feed::read_from(feed)?;
+    Ok(feed
+        .entries()
+        .iter()
+        .map(|entry| {
+            let link = entry
+                .links()
+                .iter()
+                .find(|link| link.rel() == "alternate")
+                .or(entry.links().first())
+                .map(|link| link.href().into());
+            Entry {
+                title: entry.title().value.trim().into(),
+                published: (*entry.published().unwrap_or(entry.updated())).into(),
+                link,
+                description: entry.summary().map(|summary| summary.value.clone()),
+                content: entry
+                    .content()
+                    .and_then(|content| content.value())
+                    .map(String::from),
+                categories: entry
+                    .categories()
+                    .iter()
+                    .map(|category| category.label().unwrap_or(category.term()).into())
+                    .collect(),
+            }
+        })
+        .collect())
+}
+
+/// Escapes the plain text and puts every line of it in a paragraph
+fn text_to_html(text: &str) -> String {
+    text.lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .map(|line| {
+            let line = line
+                .replace('&', "&amp;")
+                .replace('<', "&lt;")
+                .replace('>', "&gt;");
+            format!("<p>{line}</p>")
+        })
+        .collect()
+}
+
+#[derive(Deserialize)]
+struct JsonFeed {
+    items: Vec<JsonItem>,
+}
+
+#[derive(Deserialize)]
+struct JsonItem {
+    url: Option<String>,
+    title: Option<String>,
+    content_html: Option<String>,
+    content_text: Option<String>,
+    summary: Option<String>,
+    date_published: Option<String>,
+    date_modified: Option<String>,
+    #[serde(default)]
+    tags: Vec<String>,
+}
+
+fn read_json(feed: &[u8]) -> Result<Vec<Entry>, Box<dyn Error>> {
+    let feed = serde_json::from_slice::<JsonFeed>(feed)?;
+    Ok(feed
+        .items
+        .into_iter()
+        .filter_map(|item| {
+            let date = item.date_published.or(item.date_modified)?;
+            Some(Entry {
+                title: item.title?,
+                published: DateTime::parse_from_rfc3339(&date).ok()?.into(),
+                link: item.url,
+                description: item.summary,
+                content: item
+                    .content_html
+                    .or(item.content_text.as_deref().map(text_to_html)),
+                categories: item.tags,
+            })
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const RSS: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<?xml-stylesheet type="text/xsl" href="/rss.xsl"?>
+<!-- Generated by WordPress -->
+<rss version="2.0"><channel><title>Vesti</title><link>https://vesti.rs</link><description/>
+<item>
+    <title>Prva vest</title>
+    <link>https://vesti.rs/prva</link>
+    <pubDate>Sat, 17 Oct 2026 10:00:00 +0200</pubDate>
+    <category> Srbija </category>
+</item>
+</channel></rss>"#;
+
+    const ATOM: &str = r#"<?xml version="1.0" encoding="utf-8"?>
+<feed xmlns="http://www.w3.org/2005/Atom">
+<title>Vesti</title><id>urn:vesti</id><updated>2026-10-17T12:00:00Z</updated>
+<entry>
+    <title> Objavljena </title><id>urn:1</id>
+    <link rel="alternate" href="https://vesti.rs/1"/>
+    <published>2026-10-17T08:00:00Z</published>
+    <updated>2026-10-17T11:00:00Z</updated>
+    <content type="html">&lt;p&gt;Ceo tekst&lt;/p&gt;</content>
+</entry>
+<entry>
+    <title>Izmenjena</title><id>urn:2</id>
+    <link rel="alternate" href="https://vesti.rs/2"/>
+    <updated>2026-10-17T09:00:00Z</updated>
+    <summary>Kratko</summary>
+</entry>
+</feed>"#;
+
+    const JSON: &str = r#"{
+        "version": "https://jsonfeed.org/version/1.1",
+        "title": "Vesti",
+        "items": [
+            {
+                "id": "1",
+                "url": "https://vesti.rs/1",
+                "title": "Tekst",
+                "content_text": "Prvi red\n\nDrugi <red> & kraj",
+                "date_published": "2026-10-17T10:00:00+02:00",
+                "tags": ["Srbija"]
+            },
+            { "id": "2", "title": "Bez datuma" }
+        ]
+    }"#;
+
+    fn time(rfc3339: &str) -> DateTime<Local> {
+        DateTime::parse_from_rfc3339(rfc3339).unwrap().into()
+    }
+
+    #[test]
+    fn detects_the_format() {
+        let with_bom = [b"\xEF\xBB\xBF".as_slice(), RSS.as_bytes()].concat();
+        assert!(matches!(Format::detect(&with_bom), Format::Rss));
+        assert!(matches!(Format::detect(RSS.as_bytes()), Format::Rss));
+        assert!(matches!(Format::detect(ATOM.as_bytes()), Format::Atom));
+        let json = format!("\u{feff}\n  {JSON}");
+        assert!(matches!(Format::detect(json.as_bytes()), Format::Json));
+    }
+
+    #[test]
+    fn reads_rss_after_a_prelude() {
+        let with_bom = [b"\xEF\xBB\xBF".as_slice(), RSS.as_bytes()].concat();
+        let entries = read_entries(&with_bom).unwrap();
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].title, "Prva vest");
+        assert_eq!(entries[0].published, time("2026-10-17T08:00:00Z"));
+        assert_eq!(entries[0].categories, ["Srbija"]);
+    }
+
+    #[test]
+    fn reads_atom() {
+        let entries = read_entries(ATOM.as_bytes()).unwrap();
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].title, "Objavljena");
+        // Published when it has it, updated otherwise
+        assert_eq!(entries[0].published, time("2026-10-17T08:00:00Z"));
+        assert_eq!(entries[1].published, time("2026-10-17T09:00:00Z"));
+        assert_eq!(entries[0].content.as_deref(), Some("<p>Ceo tekst</p>"));
+        assert_eq!(entries[1].content, None);
+        assert_eq!(entries[1].description.as_deref(), Some("Kratko"));
+        assert_eq!(entries[1].link.as_deref(), Some("https://vesti.rs/2"));
+    }
+
+    #[test]
+    fn reads_json_feed_text() {
+        let entries = read_entries(JSON.as_bytes()).unwrap();
+        // The item without a date is skipped
+        assert_eq!(entries.len(), 1);
+        assert_eq!(
+            entries[0].content.as_deref(),
+            Some("<p>Prvi red</p><p>Drugi &lt;red&gt; &amp; kraj</p>")
+        );
+        assert_eq!(entries[0].categories, ["Srbija"]);
+    }
+}
@@ -0,0 +1,413 @@
+use std::{collections::HashMap, error::Error, fmt::Display};
+
+use ego_tree::NodeId;
+use scraper::{ElementRef, Html, Selector};
+
+use crate::{FeedItem, frontend::ComponentKind};
+
+use super::{BackendError, Client, NewsSite, Parser};
+
+/// Never part of the article
+const SKIPPED_TAGS: &[&str] = &[
+    "script", "style", "noscript", "template", "nav", "footer", "aside", "form", "button",
+    "iframe", "svg", "figure", "h1",
+];
+/// Classes and ids of the boxes around the article (menus, share buttons, related articles...)
+const UNLIKELY: &[&str] = &[
+    "comment",
+    "footer",
+    "header",
+    "menu",
+    "nav",
+    "sidebar",
+    "share",
+    "social",
+    "related",
+    "promo",
+    "advert",
+    "banner",
+    "cookie",
+    "newsletter",
+    "subscribe",
+    "popup",
+    "breadcrumb",
+    "widget",
+    "caption",
+];
+/// Classes and ids of the article itself
+const LIKELY: &[&str] = &[
+    "article", "content", "body", "main", "post", "text", "story", "entry",
+];
+/// Classes of the lead
+const LEAD: &[&str] = &["lead", "intro", "excerpt", "summary", "perex", "standfirst"];
+/// Shorter paragraphs (dates, bylines, buttons...) don't count when looking for the article
+const MIN_PARAGRAPH_LEN: usize = 25;
+
+/// Reads the articles of the sites without a parser
+pub struct Generic {
+    domain: String,
+}
+
+impl Generic {
+    pub fn new(domain: &str) -> Self {
+        Self {
+            domain: domain.into(),
+        }
+    }
+}
+
+impl Display for Generic {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.domain)
+    }
+}
+
+impl NewsSite for Generic {
+    /// There is no feed, the articles are opened by their url
+    fn get_feed_items(&self, _client: &Client) -> Result<Vec<FeedItem>, Box<dyn Error>> {
+        Ok(Vec::new())
+    }
+}
+
+impl Parser for Generic {
+    fn parse_article_content(&self, elem: ElementRef) -> Option<ComponentKind> {
+        parse_content(elem)
+    }
+
+    fn parse_article(&self, html: Html) -> Result<Vec<ComponentKind>, BackendError> {
+        extract(html)
+    }
+}
+
+fn normalized_text(elem: ElementRef) -> String {
+    elem.text()
+        .flat_map(str::split_whitespace)
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+fn text_len(elem: ElementRef) -> usize {
+    elem.text()
+        .flat_map(str::split_whitespace)
+        .map(|word| word.chars().count() + 1)
+        .sum()
+}
+
+/// How much of the text is in links (menus and lists of related articles are mostly links)
+fn link_density(elem: ElementRef) -> f64 {
+    let len = text_len(elem);
+    if len == 0 {
+        return 0.0;
+    }
+    let links = Selector::parse("a").unwrap();
+    let link_len = elem.select(&links).map(text_len).sum::<usize>();
+    link_len as f64 / len as f64
+}
+
+fn class_and_id(elem: ElementRef) -> String {
+    let value = elem.value();
+    let class = value.attr("class").unwrap_or_default();
+    let id = value.attr("id").unwrap_or_default();
+    format!("{class} {id}").to_lowercase()
+}
+
+fn matches_any(elem: ElementRef, names: &[&str]) -> bool {
+    let class_and_id = class_and_id(elem);
+    names.iter().any(|name| class_and_id.contains(name))
+}
+
+fn is_skipped(elem: ElementRef) -> bool {
+    let name = elem.value().name();
+    // The classes of the whole page (like "sticky-header") say nothing about the article
+    if name == "body" || name == "html" {
+        return false;
+    }
+    SKIPPED_TAGS.contains(&name) || (matches_any(elem, UNLIKELY) && !matches_any(elem, LIKELY))
+}
+
+fn class_weight(elem: ElementRef) -> f64 {
+    let mut weight = 0.0;
+    if matches_any(elem, LIKELY) {
+        weight += 25.0;
+    }
+    if matches_any(elem, UNLIKELY) {
+        weight -= 25.0;
+    }
+    weight
+}
+
+fn initial_score(elem: ElementRef) -> f64 {
+    let tag_weight = match elem.value().name() {
+        "article" => 10.0,
+        "div" | "section" | "main" => 5.0,
+        "pre" | "td" | "blockquote" => 3.0,
+        "ol" | "ul" | "dl" | "dd" | "dt" | "li" => -3.0,
+        "h2" | "h3" | "h4" | "h5" | "h6" | "th" => -5.0,
+        _ => 0.0,
+    };
+    tag_weight + class_weight(elem)
+}
+
+/// Scores the elements by the paragraphs in them, like Readability does. Long paragraphs with
+/// many commas are likely to be the text of the article, and so are the elements around them.
+fn score(html: &Html) -> HashMap<NodeId, f64> {
+    let paragraphs = Selector::parse("p, pre, td").unwrap();
+    let mut scores = HashMap::new();
+    for paragraph in html.select(&paragraphs) {
+        let is_hidden = paragraph
+            .ancestors()
+            .filter_map(ElementRef::wrap)
+            .any(is_skipped);
+        let len = text_len(paragraph);
+        if is_hidden || len < MIN_PARAGRAPH_LEN {
+            continue;
+        }
+        let commas = paragraph
+            .text()
+            .map(|text| text.matches(',').count())
+            .sum::<usize>();
+        let score = 1.0 + commas as f64 + (len as f64 / 100.0).min(3.0);
+
+        let ancestors = paragraph.ancestors().filter_map(ElementRef::wrap).take(2);
+        for (nth, ancestor) in ancestors.enumerate() {
+            let ancestor_score = scores
+                .entry(ancestor.id())
+                .or_insert_with(|| initial_score(ancestor));
+            // The grandparent gets half
+            *ancestor_score += score / (nth + 1) as f64;
+        }
+    }
+    for (id, score) in scores.iter_mut() {
+        if let Some(elem) = html.tree.get(*id).and_then(ElementRef::wrap) {
+            *score *= 1.0 - link_density(elem);
+        }
+    }
+    scores
+}
+
+fn parse_content(elem: ElementRef) -> Option<ComponentKind> {
+    let text = normalized_text(elem);
+    if text.is_empty() {
+        return None;
+    }
+    let paragraphs = Selector::parse("p").unwrap();
+    // A box with the whole article can have the same class
+    if matches_any(elem, LEAD) && elem.select(&paragraphs).count() <= 1 {
+        return Some(ComponentKind::Lead(text));
+    }
+    match elem.value().name() {
+        "p" | "pre" if link_density(elem) < 0.5 => Some(ComponentKind::Paragraph(text)),
+        "h2" | "h3" | "h4" => Some(ComponentKind::Subtitle(text)),
+        "blockquote" => {
+            let paragraphs = elem
+                .child_elements()
+                .filter(|child| child.value().name() == "p")
+                .map(normalized_text)
+                .filter(|text| !text.is_empty())
+                .collect::<Vec<_>>();
+            if paragraphs.is_empty() {
+                Some(ComponentKind::Boxed(vec![text]))
+            } else {
+                Some(ComponentKind::Boxed(paragraphs))
+            }
+        }
+        // Some sites write paragraphs as divs with line breaks
+        "div" | "section"
+            if elem.child_elements().all(|child| {
+                matches!(
+                    child.value().name(),
+                    "a" | "b" | "i" | "em" | "strong" | "span" | "br"
+                )
+            }) && text.chars().count() >= MIN_PARAGRAPH_LEN =>
+        {
+            Some(ComponentKind::Paragraph(text))
+        }
+        _ => None,
+    }
+}
+
+/// Turns the element into components, going into the ones that aren't components themselves
+fn extract_from(elem: ElementRef, body: &mut Vec<ComponentKind>) {
+    if is_skipped(elem) {
+        return;
+    }
+    if let Some(comp) = parse_content(elem) {
+        // There is only one lead
+        if matches!(comp, ComponentKind::Lead(_))
+            && body
+                .iter()
+                .any(|comp| matches!(comp, ComponentKind::Lead(_)))
+        {
+            return;
+        }
+        body.push(comp);
+        return;
+    }
+    for child in elem.child_elements() {
+        extract_from(child, body);
+    }
+}
+
+/// The description of the page, which is usually the lead of the article
+fn description(html: &Html) -> Option<String> {
+    let meta =
+        Selector::parse(r#"meta[property="og:description"], meta[name="description"]"#).unwrap();
+    let description = html.select(&meta).next()?.value().attr("content")?;
+    let description = description.split_whitespace().collect::<Vec<_>>().join(" ");
+    (!description.is_empty()).then_some(description)
+}
+
+/// The title of the page, for the articles that aren't from a feed
+pub fn title(html: &Html) -> Option<String> {
+    let og_title = Selector::parse(r#"meta[property="og:title"]"#).unwrap();
+    let heading = Selector::parse("h1").unwrap();
+    let title = Selector::parse("title").unwrap();
+    let og_title = html
+        .select(&og_title)
+        .next()
+        .and_then(|meta| meta.value().attr("content"))
+        .map(String::from);
+    og_title
+        .or_else(|| html.select(&heading).next().map(normalized_text))
+        .or_else(|| html.select(&title).next().map(normalized_text))
+        .map(|title| title.trim().to_string())
+        .filter(|title| !title.is_empty())
+}
+
+/// Finds the element with the text of the article and reads the lead, the paragraphs, the
+/// subtitles and the quotes from it (and from the siblings that look like a part of it)
+pub fn extract(html: Html) -> Result<Vec<ComponentKind>, BackendError> {
+    let scores = score(&html);
+    let (top, top_score) = scores
+        .iter()
+        .max_by(|(_, first), (_, second)| first.total_cmp(second))
+        .and_then(|(id, score)| Some((ElementRef::wrap(html.tree.get(*id)?)?, *score)))
+        .ok_or(BackendError::NoContent)?;
+
+    let threshold = (top_score * 0.2).max(10.0);
+    let parts = match top.parent().and_then(ElementRef::wrap) {
+        Some(parent) => parent
+            .child_elements()
+            .filter(|sibling| {
+                *sibling == top
+                    || scores
+                        .get(&sibling.id())
+                        .is_some_and(|score| *score >= threshold)
+                    || (sibling.value().name() == "p"
+                        && text_len(*sibling) >= 80
+                        && link_density(*sibling) < 0.25)
+            })
+            .collect(),
+        None => vec![top],
+    };
+    let mut body = Vec::new();
+    for part in parts {
+        extract_from(part, &mut body);
+    }
+    if !body
+        .iter()
+        .any(|comp| matches!(comp, ComponentKind::Paragraph(_)))
+    {
+        return Err(BackendError::NoContent);
+    }
+
+    if !body
+        .iter()
+        .any(|comp| matches!(comp, ComponentKind::Lead(_)))
+        && let Some(description) = description(&html)
+    {
+        // The description is often just the first paragraph
+        let beginning = description.chars().take(40).collect::<String>();
+        let is_repeated = body.iter().any(|comp| match comp {
+            ComponentKind::Paragraph(text) => text.starts_with(&beginning),
+            _ => false,
+        });
+        if !is_repeated {
+            body.insert(0, ComponentKind::Lead(description));
+        }
+    }
+    Ok(body)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PARAGRAPHS: &str = r#"
+        <p>Prvi pasus članka je dovoljno dugačak, sa nekoliko zareza, da bi se računao.</p>
+        <p>Drugi pasus, takođe dugačak, govori o nečemu drugom, ali je deo istog teksta.</p>
+        <p>Treći pasus završava članak, i ima, kao i ostali, nekoliko zareza u sebi.</p>
+    "#;
+
+    fn paragraphs(components: &[ComponentKind]) -> Vec<&str> {
+        components
+            .iter()
+            .filter_map(|comp| match comp {
+                ComponentKind::Paragraph(text) => Some(text.as_str()),
+                _ => None,
+            })
+            .collect()
+    }
+
+    fn extract_page(page: &str) -> Result<Vec<ComponentKind>, BackendError> {
+        extract(Html::parse_document(page))
+    }
+
+    #[test]
+    fn skips_the_boxes_around_the_article() {
+        let page = format!(
+            r#"<html><body>
+                <nav><p>Početna, Vesti, Sport, Kultura, Region, Svet i još mnogo toga</p></nav>
+                <div class="article-content">{PARAGRAPHS}</div>
+                <div class="related-news">
+                    <p><a href="/a">Povezan članak sa dugačkim naslovom, koji je samo link</a></p>
+                </div>
+                <footer><p>Sva prava zadržana, kopiranje sadržaja nije dozvoljeno.</p></footer>
+            </body></html>"#
+        );
+        let components = extract_page(&page).unwrap();
+        assert_eq!(paragraphs(&components).len(), 3, "{components:?}");
+        assert!(paragraphs(&components)[0].starts_with("Prvi pasus"));
+    }
+
+    #[test]
+    fn ignores_the_classes_of_the_page() {
+        let page = format!(
+            r#"<html class="no-js"><body class="home sticky-header wp-custom-logo">
+                <div class="entry">{PARAGRAPHS}</div>
+            </body></html>"#
+        );
+        let components = extract_page(&page).unwrap();
+        assert_eq!(paragraphs(&components).len(), 3, "{components:?}");
+    }
+
+    #[test]
+    fn reads_the_lead_and_the_description() {
+        let with_lead = format!(
+            r#"<html><body><article>
+                <p class="lead">Kratak uvod u članak.</p>
+                <h2>Podnaslov</h2>
+                {PARAGRAPHS}
+            </article></body></html>"#
+        );
+        let components = extract_page(&with_lead).unwrap();
+        assert_eq!(
+            components[0],
+            ComponentKind::Lead("Kratak uvod u članak.".into())
+        );
+        assert!(components.contains(&ComponentKind::Subtitle("Podnaslov".into())));
+
+        let with_description = format!(
+            r#"<html><head><meta property="og:description" content="Opis stranice"></head>
+                <body><article>{PARAGRAPHS}</article></body></html>"#
+        );
+        let components = extract_page(&with_description).unwrap();
+        assert_eq!(components[0], ComponentKind::Lead("Opis stranice".into()));
+    }
+
+    #[test]
+    fn no_content() {
+        let page = r#"<html><body><div class="menu"><a href="/">Početna</a></div></body></html>"#;
+        assert!(matches!(extract_page(page), Err(BackendError::NoContent)));
+    }
+}
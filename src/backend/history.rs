@@ -0,0 +1,43 @@
+use std::collections::HashMap;
+
+use chrono::{DateTime, Local, TimeDelta};
+use serde::{Deserialize, Serialize};
+
+use super::store;
+
+/// The ids of the articles that were read, with the time they were read at
+#[derive(Default, Serialize, Deserialize)]
+pub struct ReadArticles {
+    read: HashMap<String, DateTime<Local>>,
+}
+
+impl ReadArticles {
+    /// Articles read before this are forgotten, since they are long gone from the feeds
+    const MAX_AGE: TimeDelta = TimeDelta::days(60);
+
+    const FILE: &str = "read.json";
+
+    pub fn load() -> Self {
+        let mut read_articles = store::load::<Self>(Self::FILE);
+        let oldest = Local::now() - Self::MAX_AGE;
+        read_articles.read.retain(|_, read_at| *read_at > oldest);
+        read_articles
+    }
+
+    pub fn contains(&self, id: &str) -> bool {
+        self.read.contains_key(id)
+    }
+
+    pub fn insert<'a>(&mut self, ids: impl Iterator<Item = &'a str>) {
+        let now = Local::now();
+        for id in ids {
+            self.read.entry(id.into()).or_insert(now);
+        }
+        store::save(Self::FILE, self);
+    }
+
+    pub fn remove(&mut self, id: &str) {
+        self.read.remove(id);
+        store::save(Self::FILE, self);
+    }
+}
@@ -0,0 +1,116 @@
+use std::{
+    collections::HashMap,
+    error::Error,
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use reqwest::{
+    StatusCode,
+    blocking::RequestBuilder,
+    header::{ETAG, IF_MODIFIED_SINCE, IF_NONE_MATCH, LAST_MODIFIED},
+};
+
+#[derive(Default)]
+struct Validators {
+    etag: Option<String>,
+    last_modified: Option<String>,
+}
+
+/// The validators of the responses whose content isn't in the feed yet. They are sent back only
+/// after they are accepted, otherwise content that was never shown would get a 304 forever.
+#[derive(Default)]
+pub struct PendingValidators(HashMap<String, Validators>);
+
+impl PendingValidators {
+    pub fn extend(&mut self, other: Self) {
+        self.0.extend(other.0);
+    }
+}
+
+/// A HTTP client that remembers the `ETag` and `Last-Modified` headers of every accepted request
+/// and sends them back, so that unchanged feeds aren't downloaded again
+#[derive(Clone)]
+pub struct Client {
+    inner: reqwest::blocking::Client,
+    validators: Arc<Mutex<HashMap<String, Validators>>>,
+    pending: Arc<Mutex<PendingValidators>>,
+}
+
+impl Client {
+    pub fn new(timeout: Duration) -> Self {
+        Self {
+            inner: reqwest::blocking::Client::builder()
+                .timeout(timeout)
+                .build()
+                .unwrap_or_default(),
+            validators: Arc::default(),
+            pending: Arc::default(),
+        }
+    }
+
+    /// A client with the same accepted validators, whose responses are pending on their own
+    pub fn session(&self) -> Self {
+        Self {
+            pending: Arc::default(),
+            ..self.clone()
+        }
+    }
+
+    /// The validators of the responses since the last call
+    pub fn take_pending(&self) -> PendingValidators {
+        std::mem::take(&mut self.pending.lock().unwrap())
+    }
+
+    /// Sends the validators back from now on, once the content of their responses is used
+    pub fn accept(&self, pending: PendingValidators) {
+        self.validators.lock().unwrap().extend(pending.0);
+    }
+
+    /// Returns `None` if the resource didn't change since the last request
+    pub fn get(&self, url: &str) -> Result<Option<Vec<u8>>, Box<dyn Error>> {
+        self.send(url.into(), self.inner.get(url))
+    }
+
+    /// Returns `None` if the resource didn't change since the last request
+    pub fn post(&self, url: &str, body: &'static str) -> Result<Option<Vec<u8>>, Box<dyn Error>> {
+        self.send(format!("{url} {body}"), self.inner.post(url).body(body))
+    }
+
+    fn send(
+        &self,
+        key: String,
+        mut request: RequestBuilder,
+    ) -> Result<Option<Vec<u8>>, Box<dyn Error>> {
+        if let Some(validators) = self.validators.lock().unwrap().get(&key) {
+            if let Some(etag) = &validators.etag {
+                request = request.header(IF_NONE_MATCH, etag);
+            }
+            if let Some(last_modified) = &validators.last_modified {
+                request = request.header(IF_MODIFIED_SINCE, last_modified);
+            }
+        }
+
+        let response = request.send()?;
+        if response.status() == StatusCode::NOT_MODIFIED {
+            log::debug!("{key} wasn't modified");
+            return Ok(None);
+        }
+        let response = response.error_for_status()?;
+
+        let header = |name| {
+            response
+                .headers()
+                .get(name)
+                .and_then(|value| value.to_str().ok())
+                .map(String::from)
+        };
+        let validators = Validators {
+            etag: header(ETAG),
+            last_modified: header(LAST_MODIFIED),
+        };
+        let body = response.bytes()?.to_vec();
+        self.pending.lock().unwrap().0.insert(key, validators);
+        Ok(Some(body))
+    }
+}
@@ -0,0 +1,231 @@
+use std::{cmp::Reverse, collections::HashMap, sync::Arc};
+
+use chrono::{DateTime, Local};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    Body, FeedItem,
+    frontend::{ComponentKind, fold},
+};
+
+use super::{NewsSite, deserialize_parser, serialize_parser, store};
+
+/// Only what the search needs, the article itself is fetched again (or read from the cache)
+#[derive(Serialize, Deserialize)]
+struct Document {
+    id: String,
+    /// The articles without one (like the ones from Insajder's API) are shown from the text
+    url: Option<String>,
+    title: String,
+    published: DateTime<Local>,
+    #[serde(
+        serialize_with = "serialize_parser",
+        deserialize_with = "deserialize_parser"
+    )]
+    parser: Arc<dyn NewsSite>,
+    text: String,
+}
+
+impl Document {
+    fn to_item(&self) -> FeedItem {
+        let body = match &self.url {
+            Some(url) => Body::ToFetch { url: url.clone() },
+            None => {
+                let mut content = vec![ComponentKind::Title(self.title.clone())];
+                content.extend(
+                    self.text
+                        .lines()
+                        .map(|line| ComponentKind::Paragraph(line.into())),
+                );
+                Body::Saved {
+                    id: self.id.clone(),
+                    content,
+                }
+            }
+        };
+        FeedItem {
+            title: self.title.clone(),
+            published: self.published,
+            body,
+            parser: self.parser.clone(),
+            categories: Vec::new(),
+            read: false,
+            new: false,
+        }
+    }
+}
+
+/// Full-text index of the fetched articles. It is saved as one document per line, so that adding
+/// an article only appends to the file.
+#[derive(Default)]
+pub struct ArticleIndex {
+    /// Oldest first
+    documents: Vec<Document>,
+    /// Every word with the documents it is in and how many times
+    words: HashMap<String, Vec<(usize, u32)>>,
+}
+
+pub struct Hit {
+    pub item: FeedItem,
+    pub snippet: String,
+}
+
+fn words(text: &str) -> impl Iterator<Item = String> {
+    text.split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .map(|word| word.chars().flat_map(fold).collect())
+}
+
+/// The part of the text around the first word that starts with one of the query words
+fn snippet(text: &str, query: &[String]) -> String {
+    const BEFORE: usize = 40;
+    const LEN: usize = 140;
+    let chars = text.chars().collect::<Vec<_>>();
+    // With the index of the char each folded letter is from, since љ is folded to two letters
+    let (folded, origins): (Vec<_>, Vec<_>) = chars
+        .iter()
+        .enumerate()
+        .flat_map(|(index, &c)| fold(c).map(move |folded| (folded, index)))
+        .unzip();
+    let found = (0..folded.len())
+        .filter(|&pos| pos == 0 || !folded[pos - 1].is_alphanumeric())
+        .find(|&pos| {
+            query.iter().any(|word| {
+                let word = word.chars().collect::<Vec<_>>();
+                folded[pos..].starts_with(&word)
+            })
+        })
+        .map_or(0, |pos| origins[pos]);
+    let start = found.saturating_sub(BEFORE);
+    let end = (start + LEN).min(chars.len());
+    let mut snippet = chars[start..end]
+        .iter()
+        .map(|&c| if c == '\n' { ' ' } else { c })
+        .collect::<String>();
+    if start > 0 {
+        snippet.insert(0, '…');
+    }
+    if end < chars.len() {
+        snippet.push('…');
+    }
+    snippet
+}
+
+impl ArticleIndex {
+    /// The oldest articles get dropped after this many
+    const MAX_DOCUMENTS: usize = 2000;
+
+    const FILE: &str = "index.jsonl";
+
+    pub fn load() -> Self {
+        let mut index = Self {
+            documents: store::load_lines(Self::FILE),
+            words: HashMap::new(),
+        };
+        index.build_words();
+        index
+    }
+
+    fn build_words(&mut self) {
+        self.words.clear();
+        for index in 0..self.documents.len() {
+            self.index_words(index);
+        }
+    }
+
+    fn index_words(&mut self, index: usize) {
+        let document = &self.documents[index];
+        let mut counts = HashMap::<String, u32>::new();
+        for word in words(&document.title).chain(words(&document.text)) {
+            *counts.entry(word).or_default() += 1;
+        }
+        for (word, count) in counts {
+            self.words.entry(word).or_default().push((index, count));
+        }
+    }
+
+    pub fn contains(&self, id: &str) -> bool {
+        self.documents.iter().any(|document| document.id == id)
+    }
+
+    /// Adds the article unless it is in the index already
+    pub fn add(&mut self, item: &FeedItem, content: &[ComponentKind]) {
+        if self.contains(item.id()) {
+            return;
+        }
+        let text = content
+            .iter()
+            .filter_map(|comp| match comp {
+                ComponentKind::Title(_) => None,
+                ComponentKind::Lead(text)
+                | ComponentKind::Subtitle(text)
+                | ComponentKind::Paragraph(text) => Some(text.clone()),
+                ComponentKind::Boxed(rows) => Some(rows.join("\n")),
+            })
+            .collect::<Vec<_>>()
+            .join("\n");
+        let document = Document {
+            id: item.id().into(),
+            url: item.url().map(String::from),
+            title: item.title.clone(),
+            published: item.published,
+            parser: item.parser.clone(),
+            text,
+        };
+        // Dropping from the front moves every document, so it's done in batches
+        if self.documents.len() >= Self::MAX_DOCUMENTS + Self::MAX_DOCUMENTS / 10 {
+            self.documents.push(document);
+            let num_dropped = self.documents.len() - Self::MAX_DOCUMENTS;
+            self.documents.drain(..num_dropped);
+            self.build_words();
+            store::save_lines(Self::FILE, &self.documents);
+        } else {
+            store::append_line(Self::FILE, &document);
+            self.documents.push(document);
+            self.index_words(self.documents.len() - 1);
+        }
+    }
+
+    /// Finds the articles that have words starting with every word of the query, best first
+    pub fn search(&self, query: &str) -> Vec<Hit> {
+        let query = words(query).collect::<Vec<_>>();
+        if query.is_empty() {
+            return Vec::new();
+        }
+        let mut scores = HashMap::<usize, (usize, u32)>::new();
+        for (nth, query_word) in query.iter().enumerate() {
+            let mut found = HashMap::<usize, u32>::new();
+            for (_, documents) in self
+                .words
+                .iter()
+                .filter(|(word, _)| word.starts_with(query_word.as_str()))
+            {
+                for &(index, count) in documents {
+                    *found.entry(index).or_default() += count;
+                }
+            }
+            for (index, count) in found {
+                let score = scores.entry(index).or_default();
+                // Only the documents that had all the previous words count
+                if score.0 == nth {
+                    *score = (nth + 1, score.1 + count);
+                }
+            }
+        }
+        let mut hits = scores
+            .into_iter()
+            .filter(|(_, (num_words, _))| *num_words == query.len())
+            .map(|(index, (_, score))| (index, score))
+            .collect::<Vec<_>>();
+        hits.sort_by_key(|&(index, score)| (Reverse(score), Reverse(index)));
+        hits.into_iter()
+            .map(|(index, _)| {
+                let document = &self.documents[index];
+                Hit {
+                    item: document.to_item(),
+                    snippet: snippet(&document.text, &query),
+                }
+            })
+            .collect()
+    }
+}
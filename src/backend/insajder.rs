@@ -1,7 +1,6 @@
-use std::{error::Error, fmt::Display, rc::Rc};
+use std::{error::Error, fmt::Display, sync::Arc};
 
 use chrono::{Local, NaiveDateTime};
-use reqwest::blocking::Client;
 use scraper::{ElementRef, Html};
 use serde::Deserialize;
 
@@ -9,7 +8,7 @@ use crate::Body;
 use crate::FeedItem;
 use crate::frontend::ComponentKind;
 
-use super::{BackendError, NewsSite, parsers::Parser};
+use super::{BackendError, Client, NewsSite, generic, parsers::Parser};
 
 pub struct Insajder;
 
@@ -41,8 +40,10 @@ impl NewsSite for Insajder {
     fn get_feed_items(&self, client: &Client) -> Result<Vec<FeedItem>, Box<dyn Error>> {
         const URL: &str = "https://insajder2-hasura.superdesk.org/v1/graphql";
         const QUERY: &str = "{\"query\": \"{items:swp_article(limit:50,offset:0,order_by:{published_at:desc}){lead published_at title body}}\"}";
-        let data = client.post(URL).body(QUERY).send()?;
-        let json = data.json::<Data>()?;
+        let Some(data) = client.post(URL, QUERY)? else {
+            return Ok(Vec::new());
+        };
+        let json = serde_json::from_slice::<Data>(&data)?;
         Ok(json
             .data
             .items
@@ -57,7 +58,10 @@ impl NewsSite for Insajder {
                     html: i.body,
                     lead: i.lead,
                 },
-                parser: Rc::new(Self),
+                parser: Arc::new(Self),
+                categories: Vec::new(),
+                read: false,
+                new: false,
             })
             .collect())
     }
@@ -74,12 +78,20 @@ impl Parser for Insajder {
     }
 
     fn parse_article(&self, html: Html) -> Result<Vec<ComponentKind>, BackendError> {
+        // The API gives only the body of the article, the pages opened by their url are whole
+        if html
+            .root_element()
+            .child_elements()
+            .any(|elem| elem.value().name() == "body")
+        {
+            return generic::extract(html);
+        }
         let body = html
             .root_element()
             .child_elements()
-            .filter_map(|elem| Some(self.parse_article_content(elem)?))
+            .filter_map(|elem| self.parse_article_content(elem))
             .collect::<Vec<_>>();
-        if body.len() == 0 {
+        if body.is_empty() {
             return Err(BackendError::NoContent);
         }
         Ok(body)
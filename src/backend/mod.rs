@@ -1,18 +1,60 @@
+mod cache;
+mod clusters;
+mod custom;
 mod danas;
+mod filter;
+mod formats;
+mod generic;
+mod history;
+mod http;
+mod index;
 mod insajder;
 mod n1;
 mod parsers;
+mod registry;
+mod saved;
+mod store;
+mod topics;
 
-use crate::{Body, Feed, FeedItem, frontend::ComponentKind};
+use crate::{Body, Feed, FeedItem, config, frontend::ComponentKind};
 use chrono::{DateTime, Local};
-use danas::Danas;
-use insajder::Insajder;
-use n1::N1;
+use custom::Custom;
+pub use filter::Filter;
+pub use history::ReadArticles;
+pub use http::{Client, PendingValidators};
+pub use index::ArticleIndex;
 use parsers::Parser;
-use reqwest::blocking::Client;
+pub use registry::Registry;
+pub use saved::SavedArticles;
 use scraper::Html;
-use serde::Deserialize;
-use std::{cmp, error::Error, fmt::Display, rc::Rc, time::Instant};
+use serde::{Deserialize, Serialize};
+use std::{
+    cell::RefCell,
+    cmp,
+    collections::{HashMap, HashSet},
+    error::Error,
+    fmt::Display,
+    rc::Rc,
+    sync::{
+        Arc,
+        atomic::{AtomicBool, Ordering},
+        mpsc::{self, Receiver, TryRecvError},
+    },
+    thread,
+    time::{Duration, Instant},
+};
+pub use topics::Topic;
+
+/// What a finished refresh changed in the shown items
+pub enum Refreshed {
+    /// This many new items are shown on top of the old ones
+    Added(usize),
+    /// New items joined the stories that were already shown, so the shown items changed
+    Regrouped,
+}
+
+/// How long a refresh waits for a single news site
+const SITE_TIMEOUT: Duration = Duration::from_secs(10);
 
 #[derive(Debug)]
 pub enum BackendError {
@@ -32,116 +74,434 @@ impl Display for BackendError {
     }
 }
 
-pub fn serialize_parser<S>(val: &Rc<dyn NewsSite>, serializer: S) -> Result<S::Ok, S::Error>
+/// How many items are kept between sessions
+const MAX_SAVED_ITEMS: usize = 300;
+
+#[derive(Serialize)]
+struct SavedFeed<'a> {
+    selected: usize,
+    items: Vec<&'a FeedItem>,
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum FeedJson {
+    Saved {
+        selected: usize,
+        items: Vec<FeedItem>,
+    },
+    // The format of feed.json used by the testdata feature
+    Items(Vec<FeedItem>),
+}
+
+pub struct Refresh {
+    started: Instant,
+    receiver: Receiver<(Vec<FeedItem>, PendingValidators)>,
+}
+
+pub fn serialize_parser<S>(val: &Arc<dyn NewsSite>, serializer: S) -> Result<S::Ok, S::Error>
 where
     S: serde::Serializer,
 {
     serializer.serialize_str(&format!("{val}"))
 }
 
-pub fn deserialize_parser<'de, D>(deserializer: D) -> Result<Rc<dyn NewsSite>, D::Error>
+pub fn deserialize_parser<'de, D>(deserializer: D) -> Result<Arc<dyn NewsSite>, D::Error>
 where
     D: serde::Deserializer<'de>,
 {
     let s = String::deserialize(deserializer)?;
-    let parsers: Vec<Rc<dyn NewsSite>> = vec![Rc::new(N1), Rc::new(Insajder), Rc::new(Danas)];
-    parsers
-        .into_iter()
-        .find(|par| par.to_string() == s)
-        .ok_or(serde::de::Error::custom(format!(
-            "Couldn't find the parser for {s}"
-        )))
+    // Saved articles have to load even if their source was removed from the config
+    Ok(Registry::get()
+        .by_badge(&s)
+        .unwrap_or_else(|| Arc::new(Custom::without_feed(s))))
 }
 
-pub trait NewsSite: Display + Parser {
+pub trait NewsSite: Display + Parser + Send + Sync {
     fn get_feed_items(&self, clinet: &Client) -> Result<Vec<FeedItem>, Box<dyn Error>>;
 }
 
 impl FeedItem {
+    /// An article that isn't from a feed, read by the parser of its site. Its title is taken from
+    /// the page.
+    pub fn from_url(url: &str) -> Result<Self, Box<dyn Error>> {
+        Ok(Self {
+            title: String::new(),
+            published: Local::now(),
+            body: Body::ToFetch { url: url.into() },
+            parser: Registry::get().for_url(url)?,
+            categories: Vec::new(),
+            read: false,
+            new: false,
+        })
+    }
+
+    /// Reads the article with the parser of its site, or with the generic extractor when that
+    /// parser can't find the content (like when the site changes its layout)
+    fn parse_article(&self, html: Html) -> Result<Vec<ComponentKind>, BackendError> {
+        self.parser.parse_article(html.clone()).or_else(|err| {
+            log::warn!(
+                "The {} parser couldn't read \"{}\" ({err}), using the generic extractor",
+                self.parser,
+                self.title
+            );
+            generic::extract(html)
+        })
+    }
+
     pub fn get_article(&self) -> Result<Vec<ComponentKind>, Box<dyn Error>> {
+        self.get_article_unless(&AtomicBool::new(false))
+    }
+
+    /// Like get_article, but the article isn't cached if it was cancelled while it was fetched
+    fn get_article_unless(
+        &self,
+        cancelled: &AtomicBool,
+    ) -> Result<Vec<ComponentKind>, Box<dyn Error>> {
         match &self.body {
             Body::Fetched { html, lead } => {
-                let mut body = vec![
-                    ComponentKind::Title(self.title.clone()),
-                    ComponentKind::Lead(lead.to_string()),
-                ];
-                let html = Html::parse_fragment(&html);
-                body.extend(self.parser.parse_article(html)?);
+                let mut body = vec![ComponentKind::Title(self.title.clone())];
+                if !lead.is_empty() {
+                    body.push(ComponentKind::Lead(lead.to_string()));
+                }
+                let html = Html::parse_fragment(html);
+                body.extend(self.parse_article(html)?);
                 Ok(body)
             }
+            Body::Saved { content, .. } => Ok(content.clone()),
             Body::ToFetch { url } => {
-                let mut body = vec![ComponentKind::Title(self.title.clone())];
+                if let Some(body) = cache::load(url) {
+                    return Ok(body);
+                }
                 let html = reqwest::blocking::get(url)?;
                 let html = html.error_for_status()?.text()?;
                 let html = Html::parse_document(&html);
-                body.extend(self.parser.parse_article(html)?);
+                let title = if self.title.is_empty() {
+                    generic::title(&html).unwrap_or_default()
+                } else {
+                    self.title.clone()
+                };
+                let mut body = vec![ComponentKind::Title(title)];
+                body.extend(self.parse_article(html)?);
+                if cancelled.load(Ordering::Relaxed) {
+                    log::info!("Not caching {url}, the loading was cancelled");
+                } else if let Err(err) = cache::store(url, &body) {
+                    log::error!("Couldn't cache {url}: {err}");
+                }
                 Ok(body)
             }
         }
     }
+
+    /// Identifies the article between refreshes and sessions
+    pub fn id(&self) -> &str {
+        match &self.body {
+            Body::Saved { id, .. } => id,
+            _ => self.url().unwrap_or(&self.title),
+        }
+    }
+
+    pub fn url(&self) -> Option<&str> {
+        match &self.body {
+            Body::Fetched { .. } | Body::Saved { .. } => None,
+            Body::ToFetch { url } => Some(url),
+        }
+    }
+
+    /// Whether the article can be opened without a connection
+    pub fn is_offline(&self) -> bool {
+        self.url().is_none_or(cache::contains)
+    }
+
+    /// Gets the article on another thread, so that the caller can show a loading screen
+    pub fn fetch_article(
+        &self,
+        cancelled: Arc<AtomicBool>,
+    ) -> Receiver<Result<Vec<ComponentKind>, String>> {
+        let (sender, receiver) = mpsc::channel();
+        let item = self.clone();
+        thread::spawn(move || {
+            let article = item.get_article_unless(&cancelled);
+            let _ = sender.send(article.map_err(|err| err.to_string()));
+        });
+        receiver
+    }
 }
 
 impl Feed {
+    /// selected is the index of the selected item in items. The index is shared with the views
+    /// opened from this feed.
+    fn with_items(
+        items: Vec<FeedItem>,
+        selected: usize,
+        client: Client,
+        index: Rc<RefCell<ArticleIndex>>,
+    ) -> Self {
+        let mut feed = Self {
+            time: Instant::now(),
+            shown: Vec::new(),
+            selected: 0,
+            items: items.into(),
+            filter: Filter::default(),
+            read_articles: ReadArticles::load(),
+            saved_articles: SavedArticles::load(),
+            index,
+            snippets: HashMap::new(),
+            clusters: Vec::new(),
+            expanded: HashSet::new(),
+            live: true,
+            client,
+            refresh: None,
+            prefetched: mpsc::channel(),
+            prefetching: Arc::new(AtomicBool::new(false)),
+        };
+        for item in feed.items.iter_mut() {
+            item.read |= feed.read_articles.contains(item.id());
+        }
+        feed.update_clusters();
+        feed.shown = feed.compute_shown(&feed.filter);
+        // The selected item could be in a collapsed story
+        feed.selected = feed
+            .shown
+            .partition_point(|&index| index < selected)
+            .min(feed.shown.len().saturating_sub(1));
+        feed
+    }
+
     pub fn selected(&self) -> &FeedItem {
-        &self.items[self.selected]
+        &self.items[self.shown[self.selected]]
+    }
+
+    /// A feed of the saved articles
+    pub fn saved(&self) -> Self {
+        let items = SavedArticles::load().into_items();
+        Self {
+            live: false,
+            ..Self::with_items(items, 0, Self::client(), self.index.clone())
+        }
+    }
+
+    /// A feed of the indexed articles that match the query, each with a snippet of its text
+    pub fn search_articles(&self, query: &str) -> Self {
+        let hits = self.index.borrow().search(query);
+        let snippets = hits
+            .iter()
+            .map(|hit| (hit.item.id().to_string(), hit.snippet.clone()))
+            .collect();
+        let items = hits.into_iter().map(|hit| hit.item).collect();
+        Self {
+            live: false,
+            snippets,
+            ..Self::with_items(items, 0, Self::client(), self.index.clone())
+        }
+    }
+
+    pub fn snippet(&self, item: &FeedItem) -> Option<&str> {
+        self.snippets.get(item.id()).map(String::as_str)
+    }
+
+    pub fn index_selected(&mut self, content: &[ComponentKind]) {
+        let item = &self.items[self.shown[self.selected]];
+        self.index.borrow_mut().add(item, content);
+    }
+
+    /// Indexes the articles that were prefetched into the cache
+    pub fn index_cached(&mut self, urls: &[String]) {
+        let mut index = self.index.borrow_mut();
+        for item in &self.items {
+            if let Some(url) = item.url().filter(|url| urls.iter().any(|u| u == url))
+                && let Some(content) = cache::load(url)
+            {
+                index.add(item, &content);
+            }
+        }
+    }
+
+    /// Whether nothing is shown (the items of the sources that aren't enabled aren't)
+    pub fn is_empty(&self) -> bool {
+        self.shown.is_empty()
+    }
+
+    pub fn is_live(&self) -> bool {
+        self.live
+    }
+
+    pub fn is_saved(&self, item: &FeedItem) -> bool {
+        self.saved_articles.contains(item.id())
+    }
+
+    pub fn save_selected(&mut self, content: Vec<ComponentKind>) {
+        let item = &self.items[self.shown[self.selected]];
+        self.saved_articles.add(item, content);
+    }
+
+    pub fn unsave_selected(&mut self) {
+        let item = &self.items[self.shown[self.selected]];
+        self.saved_articles.remove(item.id());
     }
 
-    fn get_new_items(client: &Client) -> Vec<FeedItem> {
-        let news_sites: &[Box<dyn NewsSite>] = &[Box::new(N1), Box::new(Danas), Box::new(Insajder)];
+    /// Picks up the changes made in another view
+    pub fn reload_saved(&mut self) {
+        self.saved_articles = SavedArticles::load();
+    }
+
+    /// Picks up the read marks made in another view
+    pub fn reload_read(&mut self) {
+        self.read_articles = ReadArticles::load();
+        for item in self.items.iter_mut() {
+            item.read = self.read_articles.contains(item.id());
+        }
+    }
+
+    pub fn mark_selected_read(&mut self) {
+        let item = &mut self.items[self.shown[self.selected]];
+        item.read = true;
+        self.read_articles.insert([item.id()].into_iter());
+    }
+
+    pub fn mark_selected_unread(&mut self) {
+        let item = &mut self.items[self.shown[self.selected]];
+        item.read = false;
+        self.read_articles.remove(item.id());
+    }
+
+    pub fn mark_all_read(&mut self) {
+        for item in self.items.iter_mut() {
+            item.read = true;
+        }
+        self.read_articles
+            .insert(self.items.iter().map(|item| item.id()));
+    }
+
+    pub fn remove_selected_new(&mut self) {
+        self.items[self.shown[self.selected]].new = false;
+    }
+
+    /// The validators of the sites that responded in time are returned with the items, to be
+    /// accepted once the items are in the feed
+    fn get_new_items(client: &Client) -> (Vec<FeedItem>, PendingValidators) {
+        let news_sites = Registry::get().enabled();
+        let (sender, receiver) = mpsc::channel();
+        for site in &news_sites {
+            let site = site.clone();
+            let client = client.session();
+            let sender = sender.clone();
+            thread::spawn(move || {
+                let result = site.get_feed_items(&client).map_err(|err| err.to_string());
+                // The receiver is gone if the site took longer than SITE_TIMEOUT
+                let _ = sender.send((site, result, client.take_pending()));
+            });
+        }
+        drop(sender);
+
+        let deadline = Instant::now() + SITE_TIMEOUT;
         let mut feed_items = Vec::new();
+        let mut pending = PendingValidators::default();
         let mut last_published = DateTime::<Local>::MIN_UTC.into();
-        for scr in news_sites {
-            match scr.get_feed_items(client) {
+        let mut num_done = 0;
+        while num_done < news_sites.len() {
+            let timeout = deadline.saturating_duration_since(Instant::now());
+            let Ok((site, result, site_pending)) = receiver.recv_timeout(timeout) else {
+                break;
+            };
+            num_done += 1;
+            match result {
                 Ok(new_feed_items) => {
                     if let Some(last) = new_feed_items.last() {
                         last_published = cmp::max(last_published, last.published);
                     }
-                    feed_items.extend(new_feed_items)
+                    feed_items.extend(new_feed_items);
+                    pending.extend(site_pending);
                 }
-                Err(err) => eprintln!("Couldn't get articles from {}: {err}", scr),
+                Err(err) => log::error!("Couldn't get articles from {site}: {err}"),
             }
         }
+        if num_done < news_sites.len() {
+            log::error!(
+                "{} site(s) didn't respond in {} seconds",
+                news_sites.len() - num_done,
+                SITE_TIMEOUT.as_secs()
+            );
+        }
         let mut feed_items = feed_items
             .into_iter()
             .filter(|item| item.published > last_published)
             .collect::<Vec<_>>();
-        feed_items.sort_by(|a, b| b.published.cmp(&a.published));
-        feed_items
+        feed_items.sort_by_key(|item| std::cmp::Reverse(item.published));
+        (feed_items, pending)
+    }
+
+    fn client() -> Client {
+        Client::new(SITE_TIMEOUT)
     }
 
+    /// Shows the saved feed right away (refreshing it in the background) or, if there isn't one,
+    /// waits for all the sites
     pub fn new() -> Result<Self, Box<dyn Error>> {
-        let client = Client::new();
-        let feed_items = Self::get_new_items(&client);
+        thread::spawn(|| {
+            cache::prune().unwrap_or_else(|err| log::error!("Couldn't prune the cache: {err}"))
+        });
+        match Self::load() {
+            Some(mut feed) if feed.num_shown() > 0 => {
+                if !config::get().no_refresh {
+                    feed.start_refresh();
+                }
+                Ok(feed)
+            }
+            // The saved feed has only the items of the sources that aren't enabled, they are kept
+            // for when they are enabled again
+            Some(feed) => Self::fetch_with(feed.items.into()),
+            None => Self::fetch(),
+        }
+    }
+
+    /// Gets the feed from the news sites, without the items of the saved feed
+    pub fn fetch() -> Result<Self, Box<dyn Error>> {
+        Self::fetch_with(Vec::new())
+    }
+
+    /// Gets the feed from the news sites, with the saved items that weren't fetched again
+    fn fetch_with(saved: Vec<FeedItem>) -> Result<Self, Box<dyn Error>> {
+        let client = Self::client();
+        let (mut feed_items, pending) = Self::get_new_items(&client);
         if feed_items.is_empty() {
             return Err(Box::new(BackendError::FeedError));
         }
-        Ok(Feed {
-            time: Instant::now(),
-            items: feed_items.into(),
-            selected: 0,
-            client,
-        })
+        client.accept(pending);
+        let fetched = feed_items
+            .iter()
+            .map(|item| item.id().to_string())
+            .collect::<HashSet<_>>();
+        feed_items.extend(
+            saved
+                .into_iter()
+                .filter(|item| !fetched.contains(item.id())),
+        );
+        feed_items.sort_by_key(|item| std::cmp::Reverse(item.published));
+        let index = Rc::new(RefCell::new(ArticleIndex::load()));
+        Ok(Self::with_items(feed_items, 0, client, index))
     }
 
-    pub fn refresh(&mut self) -> Option<usize> {
+    /// Starts fetching new items in the background (does nothing if a refresh is already running)
+    pub fn start_refresh(&mut self) {
+        // The next refresh is counted from this one, even if it is skipped
+        self.time = Instant::now();
+        if self.refresh.is_some() || !self.live {
+            return;
+        }
+        let (sender, receiver) = mpsc::channel();
+
         #[cfg(not(feature = "testdata"))]
         {
-            let all_articles = Self::get_new_items(&self.client);
-            let first = self.items.get(0)?;
-            let new_articles: Vec<FeedItem> = all_articles
-                .into_iter()
-                .take_while(|i| i.published > first.published)
-                .collect();
-            let num_new = new_articles.len();
-            self.time = Instant::now();
-            for new_article in new_articles.into_iter().rev() {
-                self.items.push_front(new_article);
-            }
-            Some(num_new)
+            let client = self.client.clone();
+            thread::spawn(move || {
+                let _ = sender.send(Self::get_new_items(&client));
+            });
         }
 
         #[cfg(feature = "testdata")]
         {
+            use insajder::Insajder;
             let test_item = FeedItem {
                 title: "(Δ) TEST Lorem Ipsum TEST".into(),
                 published: Local::now(),
@@ -149,26 +509,116 @@ impl Feed {
                     html: "<p>TEST Lorem ipsum TEST</p>".into(),
                     lead: "TEST Lorem Ipsum TEST".into(),
                 },
-                parser: Rc::new(Insajder),
+                parser: Arc::new(Insajder),
+                categories: Vec::new(),
+                read: false,
+                new: false,
             };
-            self.time = Instant::now();
-            self.items.push_front(test_item);
-            Some(1)
+            let _ = sender.send((vec![test_item], PendingValidators::default()));
         }
+
+        self.refresh = Some(Refresh {
+            started: Instant::now(),
+            receiver,
+        });
+    }
+
+    pub fn refreshing_since(&self) -> Option<Instant> {
+        self.refresh.as_ref().map(|refresh| refresh.started)
+    }
+
+    /// Adds the items from a finished refresh
+    pub fn poll_refresh(&mut self) -> Option<Refreshed> {
+        let (all_articles, pending) = match self.refresh.as_ref()?.receiver.try_recv() {
+            Ok(refreshed) => refreshed,
+            Err(TryRecvError::Empty) => return None,
+            Err(TryRecvError::Disconnected) => (Vec::new(), PendingValidators::default()),
+        };
+        self.refresh = None;
+        self.time = Instant::now();
+        let new_articles: Vec<FeedItem> = match self.items.front() {
+            Some(first) => all_articles
+                .into_iter()
+                .take_while(|i| i.published > first.published)
+                .collect(),
+            None => all_articles,
+        };
+        let num_new = new_articles.len();
+        for mut new_article in new_articles.into_iter().rev() {
+            new_article.new = true;
+            new_article.read = self.read_articles.contains(new_article.id());
+            self.items.push_front(new_article);
+        }
+        // The items are in the feed now, so the sites can answer that nothing changed
+        self.client.accept(pending);
+        Some(self.shift_shown(num_new))
+    }
+
+    /// Downloads (and caches) the given articles in the background, one by one. Does nothing if
+    /// a prefetch is already running.
+    pub fn start_prefetch<'a>(&self, items: impl Iterator<Item = &'a FeedItem>) {
+        if !self.live {
+            return;
+        }
+        let items = items
+            .filter(|item| !item.is_offline())
+            .cloned()
+            .collect::<Vec<_>>();
+        if items.is_empty() || self.prefetching.swap(true, Ordering::Relaxed) {
+            return;
+        }
+        let sender = self.prefetched.0.clone();
+        let prefetching = self.prefetching.clone();
+        thread::spawn(move || {
+            for item in items {
+                let Some(url) = item.url() else {
+                    continue;
+                };
+                match item.get_article() {
+                    Ok(_) => {
+                        let _ = sender.send(url.to_string());
+                    }
+                    Err(err) => log::error!("Couldn't prefetch {url}: {err}"),
+                }
+            }
+            prefetching.store(false, Ordering::Relaxed);
+        });
+    }
+
+    /// Returns the urls of the articles prefetched since the last call
+    pub fn poll_prefetched(&self) -> Vec<String> {
+        self.prefetched.1.try_iter().collect()
     }
 
     pub fn to_json(&self) -> Result<String, serde_json::Error> {
-        serde_json::to_string_pretty(&self.items)
+        serde_json::to_string_pretty(&SavedFeed {
+            selected: self.shown[self.selected].min(MAX_SAVED_ITEMS - 1),
+            items: self.items.iter().take(MAX_SAVED_ITEMS).collect(),
+        })
     }
 
     pub fn from_json(json: String) -> Result<Self, serde_json::Error> {
-        let client = Client::new();
-        let items = serde_json::from_str::<Vec<FeedItem>>(&json)?;
-        Ok(Self {
-            time: Instant::now(),
-            items: items.into(),
-            selected: 0,
-            client,
-        })
+        let client = Self::client();
+        let (selected, items) = match serde_json::from_str::<FeedJson>(&json)? {
+            FeedJson::Saved { selected, items } => (selected, items),
+            FeedJson::Items(items) => (0, items),
+        };
+        let index = Rc::new(RefCell::new(ArticleIndex::load()));
+        Ok(Self::with_items(items, selected, client, index))
+    }
+
+    const FILE: &str = "feed.json";
+
+    /// Loads the feed saved by the last session
+    fn load() -> Option<Self> {
+        let json = store::read(Self::FILE)?;
+        Self::from_json(json)
+            .inspect_err(|err| log::error!("Couldn't load the saved feed: {err}"))
+            .ok()
+            .filter(|feed| !feed.items.is_empty())
+    }
+
+    pub fn save(&self) -> Result<(), Box<dyn Error>> {
+        store::write(Self::FILE, &self.to_json()?)
     }
 }
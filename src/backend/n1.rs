@@ -1,8 +1,7 @@
-use std::{error::Error, fmt::Display, rc::Rc};
+use std::{error::Error, fmt::Display, sync::Arc};
 
-use super::{BackendError, FeedItem, NewsSite, Parser};
+use super::{BackendError, Client, FeedItem, NewsSite, Parser};
 use crate::frontend::ComponentKind;
-use reqwest::blocking::Client;
 use scraper::{CaseSensitivity::CaseSensitive, ElementRef, Html};
 
 pub struct N1;
@@ -15,7 +14,7 @@ impl Display for N1 {
 
 impl NewsSite for N1 {
     fn get_feed_items(&self, client: &Client) -> Result<Vec<FeedItem>, Box<dyn Error>> {
-        super::parsers::get_feed_items(client, Rc::new(Self), "https://n1info.rs/feed")
+        super::parsers::get_feed_items(client, Arc::new(Self), "https://n1info.rs/feed")
     }
 }
 
@@ -39,9 +38,7 @@ impl Parser for N1 {
         {
             return None;
         }
-        let Some(first_child) = elem.child_elements().next() else {
-            return None;
-        };
+        let first_child = elem.child_elements().next()?;
 
         if first_child
             .value()
@@ -51,9 +48,7 @@ impl Parser for N1 {
             return None;
         }
 
-        let Some(grandchild) = first_child.child_elements().next() else {
-            return None;
-        };
+        let grandchild = first_child.child_elements().next()?;
 
         if grandchild.value().name() == "h2" {
             Some(ComponentKind::Subtitle(text))
@@ -67,6 +62,6 @@ impl Parser for N1 {
     }
 
     fn parse_article(&self, html: Html) -> Result<Vec<ComponentKind>, BackendError> {
-        super::parsers::parse_article(Rc::new(Self), html, ".article-wrapper")
+        super::parsers::parse_article(Arc::new(Self), html, ".article-wrapper")
     }
 }
@@ -1,13 +1,13 @@
-use std::{error::Error, rc::Rc};
+use std::{error::Error, sync::Arc};
 
-use chrono::DateTime;
-use reqwest::blocking::Client;
-use rss::Channel;
 use scraper::{ElementRef, Html, Selector};
 
-use crate::{FeedItem, frontend::ComponentKind};
+use crate::{Body, FeedItem, frontend::ComponentKind};
 
-use super::{BackendError, NewsSite};
+use super::{
+    BackendError, Client, NewsSite,
+    formats::{Entry, read_entries},
+};
 
 pub trait Parser {
     fn parse_article_content(&self, elem: ElementRef) -> Option<ComponentKind>;
@@ -16,33 +16,43 @@ pub trait Parser {
 
 pub fn get_feed_items(
     client: &Client,
-    parser: Rc<dyn NewsSite>,
-    url: &'static str,
+    parser: Arc<dyn NewsSite>,
+    url: &str,
 ) -> Result<Vec<FeedItem>, Box<dyn Error>> {
-    let rss = client.get(url).send()?;
-    let rss = rss.error_for_status()?.bytes()?;
-    let rss = Channel::read_from(&rss[..])?;
-    Ok(rss
-        .items
+    get_feed_items_with(client, parser, url, |entry| {
+        Some(Body::ToFetch {
+            url: entry.link.clone().filter(|url| !url.contains("english"))?,
+        })
+    })
+}
+
+pub fn get_feed_items_with(
+    client: &Client,
+    parser: Arc<dyn NewsSite>,
+    url: &str,
+    get_body: impl Fn(&Entry) -> Option<Body>,
+) -> Result<Vec<FeedItem>, Box<dyn Error>> {
+    let Some(feed) = client.get(url)? else {
+        return Ok(Vec::new());
+    };
+    Ok(read_entries(&feed)?
         .iter()
-        .filter_map(|item| {
+        .filter_map(|entry| {
             Some(FeedItem {
-                title: format!("[{}] {}", parser, item.title()?),
-                published: DateTime::parse_from_rfc2822(&item.pub_date()?).ok()?.into(),
-                body: crate::Body::ToFetch {
-                    url: item
-                        .link()
-                        .and_then(|url| (!url.contains("english")).then_some(url))?
-                        .into(),
-                },
+                title: format!("[{}] {}", parser, entry.title),
+                published: entry.published,
+                body: get_body(entry)?,
                 parser: parser.clone(),
+                categories: entry.categories.clone(),
+                read: false,
+                new: false,
             })
         })
         .collect())
 }
 
 pub fn parse_article(
-    parser: Rc<dyn Parser>,
+    parser: Arc<dyn Parser>,
     html: Html,
     content_selector: &str,
 ) -> Result<Vec<ComponentKind>, BackendError> {
@@ -0,0 +1,99 @@
+use std::{
+    error::Error,
+    sync::{Arc, OnceLock},
+};
+
+use crate::config;
+
+use super::{NewsSite, custom::Custom, danas::Danas, generic::Generic, insajder::Insajder, n1::N1};
+
+static REGISTRY: OnceLock<Registry> = OnceLock::new();
+
+struct Entry {
+    /// The name in the config file
+    name: String,
+    /// The domains of the articles the site's parser can read
+    domains: &'static [&'static str],
+    site: Arc<dyn NewsSite>,
+}
+
+/// Every news site newsminal knows: the built-in ones followed by the ones from the config file
+pub struct Registry {
+    entries: Vec<Entry>,
+}
+
+impl Registry {
+    fn new() -> Self {
+        let built_in: [(&'static [&'static str], Arc<dyn NewsSite>); 3] = [
+            (&["n1info.rs"], Arc::new(N1)),
+            (&["danas.rs"], Arc::new(Danas)),
+            (&["insajder.com"], Arc::new(Insajder)),
+        ];
+        let mut entries = config::BUILT_IN_SOURCES
+            .into_iter()
+            .zip(built_in)
+            .map(|(name, (domains, site))| Entry {
+                name: name.into(),
+                domains,
+                site,
+            })
+            .collect::<Vec<_>>();
+        // Custom sources show the content from their feed, so they can't read other articles
+        entries.extend(config::get().sources.iter().map(|source| Entry {
+            name: source.name.clone(),
+            domains: &[],
+            site: Arc::new(Custom::from(source)),
+        }));
+        Self { entries }
+    }
+
+    /// Has to be called after the config is loaded
+    pub fn get() -> &'static Self {
+        REGISTRY.get_or_init(Self::new)
+    }
+
+    /// The sites that are enabled in the config file
+    pub fn enabled(&self) -> Vec<Arc<dyn NewsSite>> {
+        let config = config::get();
+        self.entries
+            .iter()
+            .filter(|entry| config.is_enabled(&entry.name))
+            .map(|entry| entry.site.clone())
+            .collect()
+    }
+
+    /// Whether the site with the badge is enabled (the sites removed from the config file aren't)
+    pub fn is_enabled(&self, badge: &str) -> bool {
+        let config = config::get();
+        self.entries
+            .iter()
+            .any(|entry| entry.site.to_string() == badge && config.is_enabled(&entry.name))
+    }
+
+    /// Finds the site by the name in its badge (how the items are saved)
+    pub fn by_badge(&self, badge: &str) -> Option<Arc<dyn NewsSite>> {
+        self.entries
+            .iter()
+            .find(|entry| entry.site.to_string() == badge)
+            .map(|entry| entry.site.clone())
+    }
+
+    /// The site whose parser can read the article at the url, or the generic extractor
+    pub fn for_url(&self, url: &str) -> Result<Arc<dyn NewsSite>, Box<dyn Error>> {
+        let url = reqwest::Url::parse(url)?;
+        let host = url.host_str().ok_or("The url has no domain")?;
+        let host = host.strip_prefix("www.").unwrap_or(host);
+        let entry = self.entries.iter().find(|entry| {
+            entry.domains.iter().any(|domain| {
+                host == *domain
+                    || host
+                        .strip_suffix(domain)
+                        .is_some_and(|subdomain| subdomain.ends_with('.'))
+            })
+        });
+        Ok(match entry {
+            Some(entry) => entry.site.clone(),
+            None => Arc::new(Generic::new(host)),
+        })
+    }
+}
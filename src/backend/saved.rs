@@ -0,0 +1,50 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{Body, FeedItem, frontend::ComponentKind};
+
+use super::store;
+
+/// Articles saved for later. They keep their content, so that they can be read even after the
+/// site takes them down.
+#[derive(Default, Serialize, Deserialize)]
+pub struct SavedArticles {
+    items: Vec<FeedItem>,
+}
+
+impl SavedArticles {
+    const FILE: &str = "saved.json";
+
+    pub fn load() -> Self {
+        store::load(Self::FILE)
+    }
+
+    pub fn contains(&self, id: &str) -> bool {
+        self.items.iter().any(|item| item.id() == id)
+    }
+
+    pub fn add(&mut self, item: &FeedItem, content: Vec<ComponentKind>) {
+        if self.contains(item.id()) {
+            return;
+        }
+        let saved_item = FeedItem {
+            body: Body::Saved {
+                id: item.id().into(),
+                content,
+            },
+            read: false,
+            new: false,
+            ..item.clone()
+        };
+        self.items.insert(0, saved_item);
+        store::save(Self::FILE, self);
+    }
+
+    pub fn remove(&mut self, id: &str) {
+        self.items.retain(|item| item.id() != id);
+        store::save(Self::FILE, self);
+    }
+
+    pub fn into_items(self) -> Vec<FeedItem> {
+        self.items
+    }
+}
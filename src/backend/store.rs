@@ -0,0 +1,90 @@
+use std::{
+    error::Error,
+    fs::{self, OpenOptions},
+    io::Write,
+    path::PathBuf,
+};
+
+use serde::{Serialize, de::DeserializeOwned};
+
+/// The files kept between sessions (the feed, the read and the saved articles, the index) are in
+/// $XDG_DATA_HOME/newsminal
+fn path(name: &str) -> Option<PathBuf> {
+    Some(dirs::data_dir()?.join("newsminal").join(name))
+}
+
+/// Returns `None` if there is no such file
+pub fn read(name: &str) -> Option<String> {
+    fs::read_to_string(path(name)?).ok()
+}
+
+fn create_path(name: &str) -> Result<PathBuf, Box<dyn Error>> {
+    let path = path(name).ok_or("Couldn't find the data directory")?;
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    Ok(path)
+}
+
+pub fn write(name: &str, contents: &str) -> Result<(), Box<dyn Error>> {
+    fs::write(create_path(name)?, contents)?;
+    Ok(())
+}
+
+/// Loads the JSON file, or the default if there is none (or it can't be parsed)
+pub fn load<T: DeserializeOwned + Default>(name: &str) -> T {
+    let Some(json) = read(name) else {
+        return T::default();
+    };
+    serde_json::from_str(&json)
+        .inspect_err(|err| log::error!("Couldn't load {name}: {err}"))
+        .unwrap_or_default()
+}
+
+/// Saves the value as JSON, logging the errors
+pub fn save<T: Serialize>(name: &str, value: &T) {
+    serde_json::to_string(value)
+        .map_err(Box::from)
+        .and_then(|json| write(name, &json))
+        .unwrap_or_else(|err| log::error!("Couldn't save {name}: {err}"));
+}
+
+/// Loads the file with a JSON value on every line, skipping the lines that can't be parsed
+pub fn load_lines<T: DeserializeOwned>(name: &str) -> Vec<T> {
+    let Some(lines) = read(name) else {
+        return Vec::new();
+    };
+    lines
+        .lines()
+        .filter_map(|line| {
+            serde_json::from_str(line)
+                .inspect_err(|err| log::error!("Couldn't load a line of {name}: {err}"))
+                .ok()
+        })
+        .collect()
+}
+
+/// Saves the values as JSON, one on every line, logging the errors
+pub fn save_lines<T: Serialize>(name: &str, values: &[T]) {
+    values
+        .iter()
+        .map(|value| serde_json::to_string(value).map(|json| json + "\n"))
+        .collect::<Result<String, _>>()
+        .map_err(Box::from)
+        .and_then(|lines| write(name, &lines))
+        .unwrap_or_else(|err| log::error!("Couldn't save {name}: {err}"));
+}
+
+/// Adds the value as JSON on a new line at the end of the file, logging the errors
+pub fn append_line<T: Serialize>(name: &str, value: &T) {
+    let append = || -> Result<(), Box<dyn Error>> {
+        let line = serde_json::to_string(value)? + "\n";
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(create_path(name)?)?;
+        file.write_all(line.as_bytes())?;
+        Ok(())
+    };
+    append().unwrap_or_else(|err| log::error!("Couldn't save {name}: {err}"));
+}
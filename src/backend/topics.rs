@@ -0,0 +1,117 @@
+use std::fmt::Display;
+
+use crate::FeedItem;
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Topic {
+    Politika,
+    Drustvo,
+    Ekonomija,
+    Svet,
+    Region,
+    Hronika,
+    Sport,
+    Kultura,
+    Tehnologija,
+    Zdravlje,
+}
+
+impl Display for Topic {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            Topic::Politika => "politika",
+            Topic::Drustvo => "društvo",
+            Topic::Ekonomija => "ekonomija",
+            Topic::Svet => "svet",
+            Topic::Region => "region",
+            Topic::Hronika => "hronika",
+            Topic::Sport => "sport",
+            Topic::Kultura => "kultura",
+            Topic::Tehnologija => "tehnologija",
+            Topic::Zdravlje => "zdravlje",
+        };
+        write!(f, "{name}")
+    }
+}
+
+/// Category names (in lowercase) the sites use, grouped under the topics
+const CATEGORIES: &[(&str, Topic)] = &[
+    // N1
+    ("vesti", Topic::Drustvo),
+    ("srbija", Topic::Drustvo),
+    ("beograd", Topic::Drustvo),
+    ("biznis", Topic::Ekonomija),
+    ("sci&tech", Topic::Tehnologija),
+    ("sci-tech", Topic::Tehnologija),
+    ("crna hronika", Topic::Hronika),
+    ("zdravlje", Topic::Zdravlje),
+    ("kultura", Topic::Kultura),
+    ("showbiz", Topic::Kultura),
+    // Danas
+    ("politika", Topic::Politika),
+    ("društvo", Topic::Drustvo),
+    ("drustvo", Topic::Drustvo),
+    ("ekonomija", Topic::Ekonomija),
+    ("hronika", Topic::Hronika),
+    ("svet", Topic::Svet),
+    ("region", Topic::Region),
+    ("sport", Topic::Sport),
+    ("život", Topic::Drustvo),
+    ("nauka", Topic::Tehnologija),
+    ("tehnologija", Topic::Tehnologija),
+    // RSS feeds of other sites
+    ("izbori", Topic::Politika),
+    ("vlada", Topic::Politika),
+    ("privreda", Topic::Ekonomija),
+    ("biznis i finansije", Topic::Ekonomija),
+    ("svijet", Topic::Svet),
+    ("balkan", Topic::Region),
+    ("fudbal", Topic::Sport),
+    ("košarka", Topic::Sport),
+    ("tenis", Topic::Sport),
+    ("film", Topic::Kultura),
+    ("muzika", Topic::Kultura),
+    ("zdravlje i medicina", Topic::Zdravlje),
+];
+
+impl Topic {
+    pub const ALL: [Topic; 10] = [
+        Topic::Politika,
+        Topic::Drustvo,
+        Topic::Ekonomija,
+        Topic::Svet,
+        Topic::Region,
+        Topic::Hronika,
+        Topic::Sport,
+        Topic::Kultura,
+        Topic::Tehnologija,
+        Topic::Zdravlje,
+    ];
+
+    pub fn of(category: &str) -> Option<Self> {
+        let category = category.trim().to_lowercase();
+        CATEGORIES
+            .iter()
+            .find(|(name, _)| *name == category)
+            .map(|(_, topic)| *topic)
+    }
+
+    /// The topic after this one, or None after the last one
+    pub fn next(topic: Option<Self>) -> Option<Self> {
+        match topic {
+            None => Some(Self::ALL[0]),
+            Some(topic) => {
+                let index = Self::ALL.iter().position(|t| *t == topic)?;
+                Self::ALL.get(index + 1).copied()
+            }
+        }
+    }
+}
+
+impl FeedItem {
+    pub fn has_topic(&self, topic: Topic) -> bool {
+        self.categories
+            .iter()
+            .any(|category| Topic::of(category) == Some(topic))
+    }
+}
@@ -0,0 +1,36 @@
+use std::path::PathBuf;
+
+use clap::{Parser, Subcommand};
+use log::LevelFilter;
+
+/// News feed in your terminal
+#[derive(Parser)]
+#[command(version, about)]
+pub struct Cli {
+    #[command(subcommand)]
+    pub command: Option<Command>,
+
+    /// Get the news only from these sources (like n1,danas)
+    #[arg(long, global = true, value_delimiter = ',')]
+    pub sources: Option<Vec<String>>,
+
+    /// Show the saved feed without getting new items (unless there is no saved feed)
+    #[arg(long, global = true)]
+    pub no_refresh: bool,
+
+    /// Read the config from this file instead of $XDG_CONFIG_HOME/newsminal/config.toml
+    #[arg(long, global = true, value_name = "FILE")]
+    pub config: Option<PathBuf>,
+
+    /// The least important messages that are logged (off, error, warn, info, debug or trace)
+    #[arg(long, global = true, value_name = "LEVEL", default_value = "debug")]
+    pub log_level: LevelFilter,
+}
+
+#[derive(Subcommand)]
+pub enum Command {
+    /// Open the article at the url
+    Read { url: String },
+    /// Print the headlines
+    List,
+}
@@ -0,0 +1,224 @@
+use crate::{
+    frontend::{LegacyColors, Theme, UserTheme},
+    input::{Keymap, KeysConfig},
+};
+use serde::Deserialize;
+use std::{
+    collections::{HashMap, HashSet},
+    error::Error,
+    fs,
+    path::PathBuf,
+    sync::OnceLock,
+};
+
+static CONFIG: OnceLock<Config> = OnceLock::new();
+
+/// The names of the built-in sources in enabled_sources
+pub const BUILT_IN_SOURCES: [&str; 3] = ["n1", "danas", "insajder"];
+/// The badges of the built-in sources, which their items are shown, filtered and saved by
+const BUILT_IN_BADGES: [&str; 3] = ["N1", "D", "Δ"];
+
+#[derive(Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Config {
+    pub sources: Vec<SourceConfig>,
+    /// Names of the sources that are fetched (all of them by default)
+    pub enabled_sources: Option<Vec<String>>,
+    /// Download every article in the feed after a refresh, so that it can be read offline
+    pub offline_mode: bool,
+    /// The script the text is shown in
+    pub script: Script,
+    /// Seconds between the automatic refreshes
+    pub refresh_interval: u64,
+    pub layout: Layout,
+    /// The name of a built-in or a user theme
+    #[serde(rename = "theme")]
+    pub theme_name: String,
+    pub themes: HashMap<String, UserTheme>,
+    /// Replaced by the themes, but still applied over the theme
+    pub colors: Option<LegacyColors>,
+    #[serde(skip)]
+    pub theme: Theme,
+    /// The key sequences of the actions that aren't bound to their default keys
+    pub keys: HashMap<String, KeysConfig>,
+    #[serde(skip)]
+    pub keymap: Keymap,
+    /// Set by --no-refresh
+    #[serde(skip)]
+    pub no_refresh: bool,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            sources: Vec::new(),
+            enabled_sources: None,
+            offline_mode: false,
+            script: Script::default(),
+            refresh_interval: 60,
+            layout: Layout::default(),
+            theme_name: "default".into(),
+            themes: HashMap::new(),
+            colors: None,
+            theme: Theme::default(),
+            keys: HashMap::new(),
+            keymap: Keymap::default(),
+            no_refresh: false,
+        }
+    }
+}
+
+#[derive(Deserialize, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct SourceConfig {
+    pub name: String,
+    pub url: String,
+}
+
+#[derive(Deserialize, Default, Clone, Copy)]
+#[serde(rename_all = "kebab-case")]
+pub enum Script {
+    #[default]
+    AsPublished,
+    Latin,
+    Cyrillic,
+}
+
+#[derive(Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Layout {
+    pub feed_width: u16,
+    pub article_width: u16,
+}
+
+impl Default for Layout {
+    fn default() -> Self {
+        Self {
+            feed_width: 50,
+            article_width: 70,
+        }
+    }
+}
+
+impl Config {
+    const MIN_WIDTH: u16 = 20;
+    const MIN_REFRESH_INTERVAL: u64 = 10;
+
+    pub fn path() -> Option<PathBuf> {
+        Some(dirs::config_dir()?.join("newsminal").join("config.toml"))
+    }
+
+    /// Loads the config from the path, or from the default path (if it exists) when there is none
+    pub fn load(path: Option<PathBuf>) -> Result<Self, Box<dyn Error>> {
+        let path = match path {
+            Some(path) => path,
+            None => match Self::path() {
+                Some(path) if path.exists() => path,
+                _ => return Ok(Self::default()),
+            },
+        };
+        let toml = fs::read_to_string(&path).map_err(|err| format!("{}: {err}", path.display()))?;
+        let config = toml::from_str::<Self>(&toml)
+            .map_err(|err| err.to_string())
+            .and_then(|mut config| {
+                config.validate()?;
+                if config.colors.is_some() {
+                    log::warn!(
+                        "The [colors] section is deprecated, set the new, selected and read styles \
+                         of a theme instead"
+                    );
+                }
+                config.theme =
+                    Theme::resolve(&config.theme_name, &config.themes, config.colors.as_ref())?;
+                config.keymap = Keymap::resolve(&config.keys)?;
+                Ok(config)
+            })
+            .map_err(|err| format!("{}: {err}", path.display()))?;
+        Ok(config)
+    }
+
+    /// Checks the values that parse but make no sense
+    fn validate(&self) -> Result<(), String> {
+        for (name, width) in [
+            ("layout.feed_width", self.layout.feed_width),
+            ("layout.article_width", self.layout.article_width),
+        ] {
+            if width < Self::MIN_WIDTH {
+                return Err(format!(
+                    "{name} has to be at least {} (it is {width})",
+                    Self::MIN_WIDTH
+                ));
+            }
+        }
+        if self.refresh_interval < Self::MIN_REFRESH_INTERVAL {
+            return Err(format!(
+                "refresh_interval has to be at least {} seconds (it is {})",
+                Self::MIN_REFRESH_INTERVAL,
+                self.refresh_interval
+            ));
+        }
+
+        let mut names = HashSet::from(BUILT_IN_SOURCES.map(String::from));
+        for source in &self.sources {
+            if source.name.trim().is_empty() {
+                return Err(format!(
+                    "the source with the url {} has no name",
+                    source.url
+                ));
+            }
+            // A custom source's name is its badge
+            if BUILT_IN_BADGES.contains(&source.name.as_str()) {
+                return Err(format!(
+                    "the source name {} is the badge of a built-in source",
+                    source.name
+                ));
+            }
+            if !names.insert(source.name.clone()) {
+                return Err(format!(
+                    "there is more than one source named {}",
+                    source.name
+                ));
+            }
+            if !source.url.starts_with("http://") && !source.url.starts_with("https://") {
+                return Err(format!(
+                    "the url of the source {} has to start with http:// or https://",
+                    source.name
+                ));
+            }
+        }
+        if self.enabled_sources.as_ref().is_some_and(Vec::is_empty) {
+            return Err("enabled_sources has to name at least one source".into());
+        }
+        for name in self.enabled_sources.iter().flatten() {
+            if !names.contains(name) {
+                let mut known = BUILT_IN_SOURCES.map(String::from).to_vec();
+                known.extend(self.sources.iter().map(|source| source.name.clone()));
+                return Err(format!(
+                    "unknown source \"{name}\" (the sources are {})",
+                    known.join(", ")
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    /// Enables only the sources (from the command line)
+    pub fn enable_only(&mut self, sources: Vec<String>) -> Result<(), String> {
+        self.enabled_sources = Some(sources);
+        self.validate()
+    }
+
+    pub fn is_enabled(&self, source_name: &str) -> bool {
+        self.enabled_sources
+            .as_ref()
+            .is_none_or(|enabled| enabled.iter().any(|name| name == source_name))
+    }
+}
+
+pub fn init(config: Config) {
+    let _ = CONFIG.set(config);
+}
+
+pub fn get() -> &'static Config {
+    CONFIG.get_or_init(Config::default)
+}
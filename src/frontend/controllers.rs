@@ -1,16 +1,52 @@
 use std::{
+    cell::RefCell,
     io::{self, Write},
-    time::Duration,
+    iter,
+    rc::Rc,
 };
 
-use crossterm::{QueueableCommand, cursor, event, style, terminal};
+use crossterm::{QueueableCommand, cursor, event::KeyEvent, style};
 
 use crate::{
-    ArticleControler, ErrorWindow, FeedControler, Runnable,
+    ArticleControler, ErrorWindow, Feed, FeedControler, FeedItem, Loaded, LoadingWindow, Runnable,
+    backend::{Filter, Topic},
     input::{Direction, View},
 };
 
-use super::FeedItemColor;
+use super::{ComponentKind, FeedItemColor, Geometry, PromptEvent, TextPad, search, spinner_frame};
+
+impl LoadingWindow<'_> {
+    /// Shows the loading window until the article is fetched, and the error window if it fails
+    pub fn load(
+        item: &FeedItem,
+        geo: &Rc<RefCell<Geometry>>,
+        mut qc: impl QueueableCommand + Write,
+    ) -> io::Result<Option<Vec<ComponentKind>>> {
+        loop {
+            let mut loading_window = LoadingWindow::build(item, geo, &mut qc)?;
+            loading_window.run(&mut qc)?;
+            match loading_window.result {
+                Some(Loaded::Article(article)) => return Ok(Some(article)),
+                Some(Loaded::Error(err)) => {
+                    ErrorWindow::build(&format!("Couldn't get article content: {err}"), geo)?
+                        .run(&mut qc)?
+                }
+                Some(Loaded::TimedOut) => {
+                    let mut error_window = ErrorWindow::build_with_retry(
+                        "Getting the article content timed out",
+                        geo,
+                    )?;
+                    error_window.run(&mut qc)?;
+                    if error_window.should_retry {
+                        continue;
+                    }
+                }
+                None => {}
+            }
+            return Ok(None);
+        }
+    }
+}
 
 impl ArticleControler<'_> {
     pub fn scroll(
@@ -29,6 +65,126 @@ impl ArticleControler<'_> {
     pub fn goto_top(&mut self) {
         self.textpad.first = 0;
     }
+
+    /// The line of every match, in order (a line is repeated for every match in it)
+    fn find_matches(&self) -> Vec<u16> {
+        let Some(query) = self.search.query() else {
+            return Vec::new();
+        };
+        self.textpad
+            .content
+            .iter()
+            .enumerate()
+            .flat_map(|(index, line)| iter::repeat_n(index as u16, search::count(line, query)))
+            .collect()
+    }
+
+    /// Scrolls to the line (a third of the way down the screen), unless it is already visible
+    fn scroll_to_line(
+        &mut self,
+        mut qc: impl QueueableCommand + Write,
+        line: u16,
+    ) -> io::Result<()> {
+        let (first, height) = (self.textpad.first, self.textpad.height());
+        if (first..first + height).contains(&line) {
+            return Ok(());
+        }
+        let target = line.saturating_sub(height / 3);
+        self.textpad
+            .scroll_by_lines(&mut qc, target as i16 - first as i16)
+    }
+
+    fn update_search_footer(&mut self, num_matches: usize) {
+        let footer = match (self.search.query(), num_matches) {
+            (Some(_), 0) => Some(self.search.prompt("[no matches]")),
+            (Some(_), _) => Some(
+                self.search
+                    .prompt(&format!("[{}/{num_matches}]", self.current_match + 1)),
+            ),
+            (None, _) if self.search.is_typing() => Some(self.search.prompt("")),
+            (None, _) => None,
+        };
+        self.textpad.set_footer(footer);
+    }
+
+    pub fn start_search(&mut self, mut qc: impl QueueableCommand + Write) -> io::Result<()> {
+        self.search.start(self.textpad.first as usize);
+        self.textpad.set_highlight(None);
+        self.update_search_footer(0);
+        self.textpad.draw(&mut qc)?;
+        Ok(())
+    }
+
+    pub fn handle_search_key(
+        &mut self,
+        mut qc: impl QueueableCommand + Write,
+        event: KeyEvent,
+    ) -> io::Result<()> {
+        let origin = self.search.origin() as u16;
+        match self.search.handle_key(event) {
+            Some(PromptEvent::Changed | PromptEvent::Cancelled) => {
+                self.textpad.set_highlight(self.search.query());
+                let matches = self.find_matches();
+                // The first match after where the search started
+                self.current_match = matches.partition_point(|&line| line < origin);
+                if self.current_match == matches.len() {
+                    self.current_match = 0;
+                }
+                self.update_search_footer(matches.len());
+                self.textpad.draw(&mut qc)?;
+                let line = matches.get(self.current_match).copied().unwrap_or(origin);
+                let first = self.textpad.first;
+                self.textpad
+                    .scroll_by_lines(&mut qc, origin as i16 - first as i16)?;
+                self.scroll_to_line(&mut qc, line)?;
+            }
+            Some(PromptEvent::Confirmed) => {
+                self.update_search_footer(self.find_matches().len());
+                self.textpad.draw(&mut qc)?;
+            }
+            None => {}
+        }
+        Ok(())
+    }
+
+    /// Scrolls to the next (or previous) match, like n and N in vim
+    pub fn next_match(
+        &mut self,
+        mut qc: impl QueueableCommand + Write,
+        dir: Direction,
+    ) -> io::Result<()> {
+        let matches = self.find_matches();
+        if matches.is_empty() {
+            return Ok(());
+        }
+        self.current_match = match dir {
+            Direction::Down => (self.current_match + 1) % matches.len(),
+            Direction::Up => (self.current_match + matches.len() - 1) % matches.len(),
+        };
+        self.update_search_footer(matches.len());
+        self.textpad.draw_footer(&mut qc)?;
+        self.scroll_to_line(&mut qc, matches[self.current_match])?;
+        Ok(())
+    }
+
+    pub fn clear_search(&mut self, mut qc: impl QueueableCommand + Write) -> io::Result<()> {
+        self.search.clear();
+        self.textpad.set_highlight(None);
+        self.update_search_footer(0);
+        self.textpad.draw(&mut qc)?;
+        Ok(())
+    }
+
+    pub fn draw_saved(&self, mut qc: impl QueueableCommand + Write) -> io::Result<()> {
+        const TEXT: &str = "[saved]";
+        let geo = self.textpad.geo.borrow();
+        qc.queue(cursor::MoveTo(
+            geo.startx + geo.width - TEXT.len() as u16,
+            0,
+        ))?
+        .queue(style::Print(TEXT))?;
+        Ok(())
+    }
 }
 
 impl FeedControler<'_> {
@@ -40,10 +196,9 @@ impl FeedControler<'_> {
                 self.textpad.scroll_by_lines(&mut qc, -lines)?;
             }
             Direction::Down => {
-                let term_height = self.textpad.geo.borrow().term_height;
+                let height = self.textpad.height();
                 let last = self.textpad.last_visible_comp();
-                let mut lines =
-                    (last.get_posy() + last.height()) - (self.textpad.first + term_height);
+                let mut lines = (last.get_posy() + last.height()) - (self.textpad.first + height);
                 if lines == 0 {
                     lines = last.height();
                 }
@@ -64,8 +219,12 @@ impl FeedControler<'_> {
             .components
             .get(self.feed.selected)
             .expect("Selected feeditem is not loaded");
-        qc.queue(cursor::MoveTo(startx, comp.get_posy() - self.textpad.first))?;
-        for line in FeedItemColor::get_styled(comp, color) {
+        let posy = self.textpad.header_height() + comp.get_posy() - self.textpad.first;
+        qc.queue(cursor::MoveTo(startx, posy))?;
+        for line in self
+            .textpad
+            .highlighted(comp, FeedItemColor::get_styled(comp, color))
+        {
             qc.queue(style::Print(line))?
                 .queue(cursor::MoveDown(1))?
                 .queue(cursor::MoveToColumn(startx))?;
@@ -126,7 +285,7 @@ impl FeedControler<'_> {
                 }
             }
             Direction::Down => {
-                if self.feed.selected < self.feed.items.len() - 1 {
+                if self.feed.selected < self.feed.num_shown() - 1 {
                     self.feed.selected += 1;
                 }
                 if self.num_to_scroll_down() != 0 {
@@ -135,15 +294,21 @@ impl FeedControler<'_> {
             }
         };
         if should_remove_new {
+            self.feed.remove_selected_new();
             self.rebuild_selected(FeedItemColor::NotNew);
         }
         self.redraw_selected(&mut qc, FeedItemColor::Selected)?;
+        if self.search.query().is_some() {
+            self.update_search_footer();
+            self.textpad.draw_footer(&mut qc)?;
+        }
         Ok(())
     }
 
     pub fn draw(&self, mut qc: impl QueueableCommand + Write) -> io::Result<()> {
         self.textpad.draw(&mut qc)?;
         self.redraw_selected(&mut qc, FeedItemColor::Selected)?;
+        self.draw_status(&mut qc)?;
         Ok(())
     }
 
@@ -160,16 +325,22 @@ impl FeedControler<'_> {
     ) -> io::Result<bool> {
         let geo = self.textpad.geo.borrow();
         let x = x as i16 - geo.startx as i16;
-        if x < 0 || x > geo.width as i16 {
+        if x < 0 || x > geo.width as i16 || y < self.textpad.header_height() {
             return Ok(false);
         }
-        self.redraw_selected(&mut qc, FeedItemColor::NotSelected)?;
-        let last_selected = self.feed.selected;
-        self.feed.selected = self
+        let y = y - self.textpad.header_height();
+        let clicked = self
             .textpad
             .components
             .items
             .partition_point(|i| y + self.textpad.first >= i.get_posy() + i.height());
+        // Below the last item of a feed shorter than the screen
+        if clicked >= self.feed.num_shown() {
+            return Ok(false);
+        }
+        self.redraw_selected(&mut qc, FeedItemColor::NotSelected)?;
+        let last_selected = self.feed.selected;
+        self.feed.selected = clicked;
         if self.feed.selected > last_selected {
             for _ in 0..self.num_to_scroll_down() {
                 self.scroll(&mut qc, Direction::Down)?;
@@ -179,72 +350,391 @@ impl FeedControler<'_> {
                 self.scroll(&mut qc, Direction::Up)?;
             }
         }
+        self.feed.remove_selected_new();
         self.rebuild_selected(FeedItemColor::NotNew);
         self.redraw_selected(&mut qc, FeedItemColor::Selected)?;
         Ok(self.feed.selected == last_selected)
     }
 
-    fn draw_refreshing(
-        &self,
+    /// Draws a spinner in the top right corner while the feed is refreshing
+    pub fn draw_status(&self, mut qc: impl QueueableCommand + Write) -> io::Result<()> {
+        let Some(started) = self.feed.refreshing_since() else {
+            return Ok(());
+        };
+        let geo = self.textpad.geo.borrow();
+        // The last column of every feed item is blank, so the spinner doesn't cover anything
+        qc.queue(cursor::MoveTo(geo.startx + geo.width - 1, 0))?
+            .queue(style::Print(spinner_frame(started)))?;
+        Ok(())
+    }
+
+    pub fn start_refresh(&mut self, mut qc: impl QueueableCommand + Write) -> io::Result<()> {
+        self.feed.start_refresh();
+        self.draw_status(&mut qc)?;
+        Ok(())
+    }
+
+    pub fn add_new_items(
+        &mut self,
         mut qc: impl QueueableCommand + Write,
-        heigth: u16,
+        num_new: usize,
     ) -> io::Result<()> {
-        const TEXT: &str = "REFRESHING...";
-        let geo = self.textpad.geo.borrow();
-        let x = geo.startx + (geo.width - TEXT.len() as u16) / 2;
-        qc.queue(cursor::MoveTo(x, heigth / 2 + 1))?
-            .queue(style::Print(TEXT))?;
+        if num_new == 0 {
+            return Ok(());
+        }
+        let new_comps = self.feed.build_shown().take(num_new);
+        self.textpad.components.push_front(new_comps);
+        self.textpad.build_components();
+        for comp in self.textpad.components.items.iter_mut().take(num_new) {
+            FeedItemColor::set_style(comp, FeedItemColor::New);
+        }
+        self.textpad.reset_content();
+        for _ in 0..num_new {
+            self.move_select(&mut qc, Direction::Down, false)?;
+        }
+        // TODO: Consider writing a more optimized draw for this situtation
         Ok(())
     }
 
-    pub fn refresh(&mut self, mut qc: impl QueueableCommand + Write) -> io::Result<()> {
-        const HEIGHT: u16 = 5;
-        qc.queue(terminal::ScrollDown(HEIGHT))?;
-        self.draw_refreshing(&mut qc, HEIGHT)?;
-        qc.flush()?;
-        let num_new = self.feed.refresh();
-        qc.queue(terminal::ScrollUp(HEIGHT))?;
-        // Clear stdin
-        while event::poll(Duration::ZERO)? {
-            let _ = event::read();
+    /// Styles the items by their read/new state and scrolls to the selected one
+    pub fn restore_state(&mut self) {
+        for (comp, item) in self
+            .textpad
+            .components
+            .items
+            .iter_mut()
+            .zip(self.feed.shown())
+        {
+            if item.read {
+                FeedItemColor::set_style(comp, FeedItemColor::Read);
+            } else if item.new {
+                FeedItemColor::set_style(comp, FeedItemColor::New);
+            }
+        }
+        self.textpad.reset_content();
+        if let Some(selected) = self.textpad.components.get(self.feed.selected) {
+            self.textpad.first = selected.get_posy();
+        }
+    }
+
+    pub fn mark_all_read(&mut self) {
+        self.feed.mark_all_read();
+        for comp in self.textpad.components.items.iter_mut() {
+            FeedItemColor::set_style(comp, FeedItemColor::Read);
         }
+        self.textpad.reset_content();
+    }
 
-        if num_new == Some(0) {
+    pub fn mark_unread(&mut self, mut qc: impl QueueableCommand + Write) -> io::Result<()> {
+        self.feed.mark_selected_unread();
+        self.rebuild_selected(FeedItemColor::Unread);
+        self.redraw_selected(&mut qc, FeedItemColor::Selected)?;
+        Ok(())
+    }
+
+    /// Rebuilds the feed view with only the items that pass the filter (returns false and
+    /// changes nothing if no item passes)
+    pub fn set_filter(&mut self, filter: Filter) -> bool {
+        if !self.feed.set_filter(filter) {
+            return false;
+        }
+        self.rebuild_shown();
+        true
+    }
+
+    /// Builds the TextPad again after the shown items changed
+    pub fn rebuild_shown(&mut self) {
+        let content = self.feed.build_shown().collect::<Vec<_>>();
+        self.textpad = TextPad::feed(content, self.textpad.geo);
+        self.textpad.set_header(self.feed.build_header());
+        self.textpad.set_highlight(self.search.query());
+        self.update_search_footer();
+        self.restore_state();
+    }
+
+    /// Expands or collapses the story of the selected item
+    pub fn toggle_story(&mut self, mut qc: impl QueueableCommand + Write) -> io::Result<()> {
+        if self.feed.toggle_selected_cluster() {
+            self.rebuild_shown();
+            self.draw(&mut qc)?;
+        }
+        Ok(())
+    }
+
+    /// Filters by the next topic that has any items, going back to all topics after the last one
+    pub fn next_topic(&mut self) {
+        let mut filter = self.feed.filter().clone();
+        loop {
+            filter.topic = Topic::next(filter.topic);
+            if self.set_filter(filter.clone()) || filter.topic.is_none() {
+                break;
+            }
+        }
+    }
+
+    /// Finds the first shown item, starting at from, whose title matches the search
+    fn find_match(&self, from: usize, dir: Direction) -> Option<usize> {
+        let query = self.search.query()?;
+        let num_shown = self.feed.num_shown();
+        let titles = self
+            .feed
+            .shown()
+            .map(|item| &item.title)
+            .collect::<Vec<_>>();
+        (0..num_shown)
+            .map(|offset| match dir {
+                Direction::Down => (from + offset) % num_shown,
+                Direction::Up => (from + num_shown - offset) % num_shown,
+            })
+            .find(|&index| search::contains(titles[index], query))
+    }
+
+    /// Selects the item at index and scrolls to it if it isn't visible
+    fn jump_to(&mut self, index: usize) {
+        self.feed.selected = index;
+        self.feed.remove_selected_new();
+        self.rebuild_selected(FeedItemColor::NotNew);
+        let comp = self
+            .textpad
+            .components
+            .get(index)
+            .expect("Selected feeditem is not loaded");
+        if comp.get_posy() < self.textpad.first
+            || comp.get_posy() + comp.height() > self.textpad.first + self.textpad.height()
+        {
+            self.textpad.first = comp.get_posy();
+        }
+    }
+
+    /// Shows the search below the feed, with the position of the selected item among the matches
+    fn update_search_footer(&mut self) {
+        let footer = match self.search.query() {
+            Some(query) => {
+                let matches = self
+                    .feed
+                    .shown()
+                    .map(|item| search::contains(&item.title, query))
+                    .collect::<Vec<_>>();
+                let total = matches.iter().filter(|&&m| m).count();
+                let info = if total == 0 {
+                    "[no matches]".into()
+                } else if matches[self.feed.selected] {
+                    let nth = matches[..=self.feed.selected]
+                        .iter()
+                        .filter(|&&m| m)
+                        .count();
+                    format!("[{nth}/{total}]")
+                } else {
+                    format!("[{total}]")
+                };
+                Some(self.search.prompt(&info))
+            }
+            None if self.search.is_typing() => Some(self.search.prompt("")),
+            None => None,
+        };
+        self.textpad.set_footer(footer);
+    }
+
+    pub fn start_search(&mut self, mut qc: impl QueueableCommand + Write) -> io::Result<()> {
+        self.search.start(self.feed.selected);
+        self.textpad.set_highlight(None);
+        self.update_search_footer();
+        self.draw(&mut qc)?;
+        Ok(())
+    }
+
+    pub fn handle_search_key(
+        &mut self,
+        mut qc: impl QueueableCommand + Write,
+        event: KeyEvent,
+    ) -> io::Result<()> {
+        match self.search.handle_key(event) {
+            Some(PromptEvent::Changed) => {
+                let found = self.find_match(self.search.origin(), Direction::Down);
+                self.jump_to(found.unwrap_or(self.search.origin()));
+            }
+            Some(PromptEvent::Cancelled) => self.jump_to(self.search.origin()),
+            Some(PromptEvent::Confirmed) => {}
+            None => return Ok(()),
+        }
+        self.textpad.set_highlight(self.search.query());
+        self.update_search_footer();
+        self.draw(&mut qc)?;
+        Ok(())
+    }
+
+    /// Jumps to the next (or previous) matching item, like n and N in vim
+    pub fn next_match(
+        &mut self,
+        mut qc: impl QueueableCommand + Write,
+        dir: Direction,
+    ) -> io::Result<()> {
+        if self.search.query().is_none() {
             return Ok(());
         }
-        if let Some(num_new) = num_new {
-            let new_comps = self.feed.items.iter().take(num_new).map(|i| i.build());
-            self.textpad.components.push_front(new_comps);
-            self.textpad.build_components();
-            for comp in self.textpad.components.items.iter_mut().take(num_new) {
-                FeedItemColor::set_style(comp, FeedItemColor::New);
+        let num_shown = self.feed.num_shown();
+        let from = match dir {
+            Direction::Down => (self.feed.selected + 1) % num_shown,
+            Direction::Up => (self.feed.selected + num_shown - 1) % num_shown,
+        };
+        if let Some(index) = self.find_match(from, dir) {
+            self.jump_to(index);
+        }
+        self.update_search_footer();
+        self.draw(&mut qc)?;
+        Ok(())
+    }
+
+    pub fn clear_search(&mut self, mut qc: impl QueueableCommand + Write) -> io::Result<()> {
+        self.search.clear();
+        self.textpad.set_highlight(None);
+        self.update_search_footer();
+        self.draw(&mut qc)?;
+        Ok(())
+    }
+
+    pub fn start_article_search(
+        &mut self,
+        mut qc: impl QueueableCommand + Write,
+    ) -> io::Result<()> {
+        self.article_search.start(0);
+        self.textpad
+            .set_footer(Some(self.article_search.prompt("[all articles]")));
+        self.draw(&mut qc)?;
+        Ok(())
+    }
+
+    pub fn handle_article_search_key(
+        &mut self,
+        mut qc: impl QueueableCommand + Write,
+        event: KeyEvent,
+    ) -> io::Result<()> {
+        match self.article_search.handle_key(event) {
+            Some(PromptEvent::Changed) => {
+                self.textpad
+                    .set_footer(Some(self.article_search.prompt("[all articles]")));
+                self.textpad.draw_footer(&mut qc)?;
+                return Ok(());
             }
-            self.textpad.reset_content();
-            for _ in 0..num_new {
-                self.move_select(&mut qc, Direction::Down, false)?;
+            Some(PromptEvent::Confirmed) => {
+                if let Some(query) = self.article_search.query().map(String::from) {
+                    self.article_search.clear();
+                    self.update_search_footer();
+                    self.open_search_results(&mut qc, &query)?;
+                }
             }
+            Some(PromptEvent::Cancelled) => {}
+            None => return Ok(()),
         }
-        // TODO: Consider writing a more optimized draw for this situtation
+        self.article_search.clear();
+        self.update_search_footer();
+        self.draw(&mut qc)?;
         Ok(())
     }
 
-    pub fn select(&mut self, mut qc: impl QueueableCommand + Write) -> io::Result<()> {
-        // TODO: Add a loading page
+    pub fn open_search_results(
+        &mut self,
+        qc: impl QueueableCommand + Write,
+        query: &str,
+    ) -> io::Result<()> {
+        let results = self.feed.search_articles(query);
+        self.open_nested(qc, results, &format!("No articles found for \"{query}\""))
+    }
+
+    pub fn mark_prefetched(&mut self, urls: &[String]) {
+        self.feed.index_cached(urls);
+        for (index, &item_index) in self.feed.shown.iter().enumerate() {
+            let item = &self.feed.items[item_index];
+            if item.url().is_some_and(|url| urls.iter().any(|u| u == url)) {
+                let comp = self.feed.build_item(item_index);
+                self.textpad.components.replace(index, comp);
+            }
+        }
+        self.textpad.reset_content();
+    }
+
+    /// Gets the selected article, showing the loading screen and any errors on the way
+    fn load_selected(
+        &mut self,
+        mut qc: impl QueueableCommand + Write,
+    ) -> io::Result<Option<Vec<ComponentKind>>> {
         self.input.clear();
-        match self.feed.selected().get_article() {
-            Ok(article) => {
-                ArticleControler::build(article, self.textpad.geo, &mut qc)?.run(&mut qc)?
+        let article = LoadingWindow::load(self.feed.selected(), self.textpad.geo, &mut qc)?;
+        if let Some(article) = &article {
+            self.feed.index_selected(article);
+        }
+        Ok(article)
+    }
+
+    fn rebuild_all(&mut self) {
+        let content = self.feed.build_shown().collect::<Vec<_>>();
+        for (index, comp) in content.into_iter().enumerate() {
+            self.textpad.components.replace(index, comp);
+        }
+        self.textpad.reset_content();
+    }
+
+    pub fn select(&mut self, mut qc: impl QueueableCommand + Write) -> io::Result<()> {
+        if let Some(article) = self.load_selected(&mut qc)? {
+            let mut article_controler =
+                ArticleControler::build(article.clone(), self.textpad.geo, &mut qc)?;
+            article_controler.run(&mut qc)?;
+            if article_controler.should_save {
+                self.feed.save_selected(article);
+                self.rebuild_all();
             }
-            Err(err) => ErrorWindow::build(
-                &format!("Couldn't get article content: {err}"),
-                self.textpad.geo,
-            )?
-            .run(&mut qc)?,
         }
         self.textpad.geo.borrow_mut().change_view(View::Feed);
+        self.feed.mark_selected_read();
         self.rebuild_selected(FeedItemColor::Read);
         // FIXME: Add self.textpad.resize();
         self.draw(&mut qc)?;
         Ok(())
     }
+
+    pub fn toggle_saved(&mut self, mut qc: impl QueueableCommand + Write) -> io::Result<()> {
+        if self.feed.is_saved(self.feed.selected()) {
+            self.feed.unsave_selected();
+        } else {
+            if let Some(article) = self.load_selected(&mut qc)? {
+                self.feed.save_selected(article);
+            }
+            self.textpad.geo.borrow_mut().change_view(View::Feed);
+        }
+        self.rebuild_all();
+        self.draw(&mut qc)?;
+        Ok(())
+    }
+
+    pub fn open_saved(&mut self, mut qc: impl QueueableCommand + Write) -> io::Result<()> {
+        let saved = self.feed.saved();
+        self.open_nested(&mut qc, saved, "There are no saved articles")?;
+        self.draw(&mut qc)?;
+        Ok(())
+    }
+
+    /// Runs the view of the saved articles or of the search results, then picks up the changes
+    /// made in it (and shows the error instead if it is empty)
+    fn open_nested(
+        &mut self,
+        mut qc: impl QueueableCommand + Write,
+        feed: Feed,
+        empty_msg: &str,
+    ) -> io::Result<()> {
+        // The nested view is a FeedControler itself, so the writer is type-erased to keep the
+        // generics from recursing
+        let mut qc: &mut dyn Write = &mut qc;
+        self.input.clear();
+        if feed.is_empty() {
+            ErrorWindow::build(empty_msg, self.textpad.geo)?.run(&mut qc)?;
+            self.textpad.geo.borrow_mut().change_view(View::Feed);
+        } else {
+            FeedControler::build(feed, self.textpad.geo, &mut qc)?.run(&mut qc)?;
+        }
+        self.feed.reload_saved();
+        self.feed.reload_read();
+        // Rebuilt from scratch, so that the read marks are restyled too
+        self.rebuild_shown();
+        Ok(())
+    }
 }
@@ -0,0 +1,144 @@
+use std::{
+    cell::RefCell,
+    io::{self, Write},
+};
+
+use crossterm::{
+    QueueableCommand, cursor,
+    event::{self, Event, KeyCode, KeyEventKind},
+    style, terminal,
+};
+
+use crate::{
+    backend, config,
+    input::{Action, View},
+};
+
+use super::Geometry;
+
+fn describe(action: Action, view: View) -> Option<String> {
+    let description = match (action, view) {
+        (Action::Quit, View::Feed) => "quit (or go back)",
+        (Action::Quit, View::Loading) => "cancel",
+        (Action::Quit, _) => "go back",
+        (Action::Up, View::Feed) => "select the previous item",
+        (Action::Up, _) => "scroll up",
+        (Action::Down, View::Feed) => "select the next item",
+        (Action::Down, _) => "scroll down",
+        (Action::Select, _) => "open the selected article",
+        (Action::Top, View::Feed) => "select the first item",
+        (Action::Top, _) => "go to the top",
+        (Action::Refresh, View::Error) => "retry",
+        (Action::Refresh, _) => "refresh the feed",
+        (Action::MarkAllRead, _) => "mark everything as read",
+        (Action::MarkUnread, _) => "mark the selected item as unread",
+        (Action::UnreadOnly, _) => "show only the unread items (or all)",
+        (Action::ToggleSource(index), _) => {
+            let site = backend::Registry::get().enabled().get(index)?.to_string();
+            return Some(format!("show or hide {site}"));
+        }
+        (Action::NextTopic, _) => "show the next topic",
+        (Action::Search, _) => "search",
+        (Action::NextMatch, _) => "go to the next match",
+        (Action::PreviousMatch, _) => "go to the previous match",
+        (Action::ClearSearch, _) => "clear the search",
+        (Action::SearchArticles, _) => "search the fetched articles",
+        (Action::ToggleStory, _) => "show all versions of the story",
+        (Action::Save, View::Feed) => "save (or unsave) the selected article",
+        (Action::Save, _) => "save the article",
+        (Action::OpenSaved, _) => "open the saved articles",
+        (Action::Help, _) => "show (or close) this help",
+    };
+    Some(description.into())
+}
+
+fn truncate(text: &str, len: usize) -> String {
+    if text.chars().count() <= len {
+        return format!("{text:<len$}");
+    }
+    let mut truncated = text.chars().take(len.saturating_sub(1)).collect::<String>();
+    truncated.push('…');
+    truncated
+}
+
+/// The boxed list of the key bindings, as wide as the view and not higher than the terminal
+fn build_help(rows: &[(String, String)], geo: &Geometry) -> Vec<String> {
+    const TITLE: &str = " Keys ";
+    let width = geo.width as usize;
+    let inner = width.saturating_sub(6);
+    let keys_width = rows
+        .iter()
+        .map(|(keys, _)| keys.chars().count())
+        .max()
+        .unwrap_or(0)
+        .min(inner / 2);
+    let description_width = inner.saturating_sub(keys_width + 2);
+
+    let mut lines = vec![format!(
+        " ┌{TITLE}{}┐ ",
+        "─".repeat(width.saturating_sub(4 + TITLE.len()))
+    )];
+    // The borders take two lines
+    let max_rows = (geo.term_height as usize).saturating_sub(2);
+    for (nth, (keys, description)) in rows.iter().enumerate() {
+        if nth + 1 == max_rows && rows.len() > max_rows {
+            lines.push(format!(" │ {} │ ", truncate("…", inner)));
+            break;
+        }
+        lines.push(format!(
+            " │ {}  {} │ ",
+            truncate(keys, keys_width),
+            truncate(description, description_width)
+        ));
+    }
+    lines.push(format!(" └{}┘ ", "─".repeat(width.saturating_sub(4))));
+    lines
+}
+
+fn draw_help(
+    rows: &[(String, String)],
+    geo: &Geometry,
+    qc: &mut (impl QueueableCommand + Write),
+) -> io::Result<()> {
+    let lines = build_help(rows, geo);
+    let style = config::get().theme.boxed;
+    let starty = geo.term_height.saturating_sub(lines.len() as u16) / 2;
+    qc.queue(cursor::MoveTo(geo.startx, starty))?;
+    for line in lines {
+        qc.queue(style::Print(style.apply(&line)))?
+            .queue(cursor::MoveDown(1))?
+            .queue(cursor::MoveToColumn(geo.startx))?;
+    }
+    qc.flush()
+}
+
+/// Shows the key bindings of the view over it, until ?, q or Escape is pressed. The view has to
+/// be rebuilt and redrawn after, since the terminal could have been resized in the meantime.
+pub fn show_help(
+    view: View,
+    geo: &RefCell<Geometry>,
+    mut qc: impl QueueableCommand + Write,
+) -> io::Result<()> {
+    let rows = config::get()
+        .keymap
+        .bindings(view)
+        .into_iter()
+        .filter_map(|(keys, action)| Some((keys, describe(action, view)?)))
+        .collect::<Vec<_>>();
+    draw_help(&rows, &geo.borrow(), &mut qc)?;
+    loop {
+        match event::read()? {
+            Event::Key(event) if event.kind == KeyEventKind::Press => {
+                if let KeyCode::Char('?' | 'q') | KeyCode::Esc = event.code {
+                    return Ok(());
+                }
+            }
+            Event::Resize(width, height) => {
+                geo.borrow_mut().resize((width, height));
+                qc.queue(terminal::Clear(terminal::ClearType::All))?;
+                draw_help(&rows, &geo.borrow(), &mut qc)?;
+            }
+            _ => {}
+        }
+    }
+}
@@ -1,20 +1,32 @@
 // TODO: Make struct with built component string in it
 mod controllers;
+mod help;
+mod script;
+mod search;
+mod theme;
 
-use crate::{ErrorWindow, FeedItem, View};
+pub use help::show_help;
+pub use script::transliterate;
+pub use search::{PromptEvent, Search, fold};
+pub use theme::{LegacyColors, Theme, UserTheme};
+
+use crate::{Action, ErrorWindow, Feed, FeedItem, LoadingWindow, View, backend, config};
 use crossterm::{
     QueueableCommand, cursor,
-    style::{self, Color, ContentStyle, Stylize},
+    style::{self, ContentStyle},
     terminal,
 };
+use serde::{Deserialize, Serialize};
 use std::{
     cell::RefCell,
     collections::VecDeque,
     io::{self, Write},
+    ops::Range,
     rc::Rc,
+    time::Instant,
 };
 
-#[derive(Debug, PartialEq)]
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
 pub enum ComponentKind {
     Title(String),
     Subtitle(String),
@@ -52,10 +64,10 @@ impl Component {
 
     fn build(&mut self, width: usize, posy: u16) {
         let lines = match &self.comp_type {
-            ComponentKind::Title(text) => Title::build(&text, width),
-            ComponentKind::Subtitle(text) => Subtitle::build(&text, width),
-            ComponentKind::Lead(text) => Lead::build(&text, width),
-            ComponentKind::Paragraph(text) => Paragraph::build(&text, width),
+            ComponentKind::Title(text) => Title::build(text, width),
+            ComponentKind::Subtitle(text) => Subtitle::build(text, width),
+            ComponentKind::Lead(text) => Lead::build(text, width),
+            ComponentKind::Paragraph(text) => Paragraph::build(text, width),
             ComponentKind::Boxed(text) => Boxed::build(&text.join("\n"), width),
         };
         self.content = ComponentState::Built(ComponentContent {
@@ -73,7 +85,7 @@ impl Component {
             ),
             ComponentState::Built(content) => (content.lines.clone(), content.style),
         };
-        let style = if style.is_some() { style } else { comp_style };
+        let style = style.or(comp_style).or_else(|| self.base_style());
         if let Some(style) = style {
             lines
                 .iter()
@@ -84,6 +96,27 @@ impl Component {
         }
     }
 
+    /// The rows of the title of a feed card, the only ones searched in the feed
+    fn title_rows(&self, width: usize) -> Range<usize> {
+        match &self.comp_type {
+            // A blank row and the top border, then the wrapped title without its blank first row
+            ComponentKind::Boxed(rows) => {
+                let title = rows.first().map(String::as_str).unwrap_or_default();
+                2..1 + wrap_paragraph(title, width - 6).len()
+            }
+            _ => 0..0,
+        }
+    }
+
+    /// The style of the component when it has no other (like the selected or the read feed items).
+    /// It isn't built into the lines, since the styles don't nest.
+    fn base_style(&self) -> Option<ContentStyle> {
+        match self.comp_type {
+            ComponentKind::Boxed(_) => Some(config::get().theme.boxed.to_content_style()),
+            _ => None,
+        }
+    }
+
     fn get_posy(&self) -> u16 {
         match &self.content {
             ComponentState::ToBuild => panic!(
@@ -139,7 +172,7 @@ impl Component {
 
 impl From<ComponentKind> for Component {
     fn from(value: ComponentKind) -> Self {
-        Self::new(value)
+        Self::new(value.transliterated(config::get().script))
     }
 }
 
@@ -187,6 +220,23 @@ impl Components {
             .collect()
     }
 
+    /// Changes the component at index, keeping its style. The components after it are moved if
+    /// its height changed (like when a longer info row wraps).
+    fn replace(&mut self, index: usize, comp_type: ComponentKind) {
+        let Some(comp) = self.items.get_mut(index) else {
+            return;
+        };
+        let (posy, height, style) = (comp.get_posy(), comp.height(), comp.get_style());
+        comp.comp_type = comp_type.transliterated(config::get().script);
+        comp.build(self.width, posy);
+        if let Some(style) = style {
+            comp.set_style(style);
+        }
+        if comp.height() != height {
+            self.build(self.width);
+        }
+    }
+
     fn push_front(&mut self, new_items: impl DoubleEndedIterator<Item = ComponentKind>) {
         for comp in new_items.rev() {
             self.items.push_front(comp.into());
@@ -233,29 +283,26 @@ pub struct Geometry {
     max_width: u16,
 }
 
-// TODO: Implement configuration
 impl Geometry {
-    const FEED_WIDTH: u16 = 50;
-    const ARTICLE_WIDTH: u16 = 70;
-
     pub fn new(term_dimens: (u16, u16)) -> Self {
         let (term_width, term_height) = term_dimens;
-        let width = Self::FEED_WIDTH.min(term_width);
+        let feed_width = config::get().layout.feed_width;
+        let width = feed_width.min(term_width);
         let startx = (term_width - width) / 2;
         Self {
             term_height,
             term_width,
             startx,
             width,
-            max_width: Self::FEED_WIDTH,
+            max_width: feed_width,
         }
     }
 
     pub fn change_view(&mut self, view: View) {
+        let layout = &config::get().layout;
         self.max_width = match view {
-            View::Feed => Self::FEED_WIDTH,
-            View::Article => Self::ARTICLE_WIDTH,
-            View::Error => Self::ARTICLE_WIDTH,
+            View::Feed => layout.feed_width,
+            View::Article | View::Error | View::Loading => layout.article_width,
         };
         self.width = self.max_width.min(self.term_width);
         self.startx = (self.term_width - self.width) / 2;
@@ -278,6 +325,14 @@ pub struct TextPad<'a> {
     components: Components,
     content: Vec<String>,
     first: u16,
+    /// A line that stays on top of the content
+    header: Option<String>,
+    /// A line that stays below the content
+    footer: Option<String>,
+    /// The search query to highlight
+    highlight: Option<String>,
+    /// Only the titles of the feed cards are highlighted, since only they are searched
+    titles_only: bool,
     pub geo: &'a Rc<RefCell<Geometry>>,
 }
 
@@ -288,17 +343,59 @@ impl<'a> TextPad<'a> {
             content: components.to_lines(),
             components,
             first: 0,
+            header: None,
+            footer: None,
+            highlight: None,
+            titles_only: false,
             geo,
         }
     }
 
+    /// A TextPad of feed cards
+    pub fn feed(components: Vec<ComponentKind>, geo: &'a Rc<RefCell<Geometry>>) -> TextPad<'a> {
+        Self {
+            titles_only: true,
+            ..Self::new(components, geo)
+        }
+    }
+
     pub fn build_components(&mut self) {
         let width = self.geo.borrow().width as usize;
         self.components.build(width);
     }
 
     pub fn reset_content(&mut self) {
-        self.content = self.components.to_lines();
+        self.content = self
+            .components
+            .items
+            .iter()
+            .flat_map(|comp| self.highlighted(comp, comp.content(None)))
+            .collect();
+    }
+
+    pub fn set_highlight(&mut self, query: Option<&str>) {
+        self.highlight = query.map(String::from);
+        self.reset_content();
+    }
+
+    /// Highlights the query in the lines of the component
+    pub(super) fn highlighted(&self, comp: &Component, lines: Vec<String>) -> Vec<String> {
+        let Some(query) = &self.highlight else {
+            return lines;
+        };
+        let rows = if self.titles_only {
+            comp.title_rows(self.components.width)
+        } else {
+            0..lines.len()
+        };
+        lines
+            .into_iter()
+            .enumerate()
+            .map(|(row, line)| match rows.contains(&row) {
+                true => search::highlight(&line, query),
+                false => line,
+            })
+            .collect()
     }
 
     pub fn build(&mut self) {
@@ -306,17 +403,64 @@ impl<'a> TextPad<'a> {
         self.reset_content();
     }
 
+    pub fn set_header(&mut self, header: Option<String>) {
+        self.header = header;
+    }
+
+    pub fn set_footer(&mut self, footer: Option<String>) {
+        self.footer = footer;
+    }
+
+    fn header_height(&self) -> u16 {
+        self.header.is_some() as u16
+    }
+
+    /// The number of rows the content is drawn on
+    fn height(&self) -> u16 {
+        self.geo
+            .borrow()
+            .term_height
+            .saturating_sub(self.header_height() + self.footer.is_some() as u16)
+    }
+
+    fn draw_header(&self, qc: &mut (impl QueueableCommand + Write)) -> io::Result<()> {
+        let Some(header) = &self.header else {
+            return Ok(());
+        };
+        let geo = self.geo.borrow();
+        let x = geo.startx + geo.width.saturating_sub(visible_len(header) as u16) / 2;
+        qc.queue(cursor::MoveTo(0, 0))?
+            .queue(terminal::Clear(terminal::ClearType::CurrentLine))?
+            .queue(cursor::MoveToColumn(x))?
+            .queue(style::Print(header))?;
+        Ok(())
+    }
+
+    pub fn draw_footer(&self, qc: &mut (impl QueueableCommand + Write)) -> io::Result<()> {
+        let Some(footer) = &self.footer else {
+            return Ok(());
+        };
+        let geo = self.geo.borrow();
+        qc.queue(cursor::MoveTo(0, geo.term_height.saturating_sub(1)))?
+            .queue(terminal::Clear(terminal::ClearType::CurrentLine))?
+            .queue(cursor::MoveToColumn(geo.startx))?
+            .queue(style::Print(footer))?;
+        Ok(())
+    }
+
     pub fn draw(&self, mut qc: impl QueueableCommand + Write) -> io::Result<()> {
         let geo = self.geo.borrow();
-        qc.queue(terminal::Clear(terminal::ClearType::All))?
-            .queue(cursor::MoveTo(geo.startx, 0))?;
+        qc.queue(terminal::Clear(terminal::ClearType::All))?;
+        self.draw_header(&mut qc)?;
+        self.draw_footer(&mut qc)?;
+        qc.queue(cursor::MoveTo(geo.startx, self.header_height()))?;
         for line in self
             .content
             .iter()
             .skip(self.first as usize)
-            .take(geo.term_height as usize)
+            .take(self.height() as usize)
         {
-            qc.write(line.as_bytes())?;
+            qc.write_all(line.as_bytes())?;
             qc.queue(cursor::MoveDown(1))?
                 .queue(cursor::MoveToColumn(geo.startx))?;
         }
@@ -328,10 +472,11 @@ impl<'a> TextPad<'a> {
         mut qc: impl QueueableCommand + Write,
         lines: i16,
     ) -> io::Result<()> {
+        let height = self.height();
         let geo = self.geo.borrow();
         let mut draw_line = 0;
         let is_up = lines < 0;
-        let mut lines = lines.abs() as u16;
+        let mut lines = lines.unsigned_abs();
 
         if is_up {
             if self.first < lines {
@@ -341,29 +486,37 @@ impl<'a> TextPad<'a> {
 
             qc.queue(terminal::ScrollDown(lines))?;
         } else {
-            let last = self.first + geo.term_height;
-            if (self.content.len() as u16) < geo.term_height {
+            let last = self.first + height;
+            if (self.content.len() as u16) < height {
                 lines = 0
             } else if (last + lines) as usize >= self.content.len() {
                 lines = self.content.len() as u16 - last;
             }
             self.first += lines;
-            draw_line = geo.term_height - lines;
+            draw_line = height.saturating_sub(lines);
 
             qc.queue(terminal::ScrollUp(lines))?;
         }
+        if lines >= height {
+            // Nothing on the screen stays, so it's simpler to draw it all again
+            drop(geo);
+            return self.draw(qc);
+        }
 
-        qc.queue(cursor::MoveTo(geo.startx, draw_line))?;
+        qc.queue(cursor::MoveTo(geo.startx, self.header_height() + draw_line))?;
         for line in self
             .content
             .iter()
             .skip((self.first + draw_line) as usize)
             .take(lines as usize)
         {
-            qc.write(line.as_bytes())?;
+            qc.write_all(line.as_bytes())?;
             qc.queue(cursor::MoveDown(1))?
                 .queue(cursor::MoveToColumn(geo.startx))?;
         }
+        // The header and footer got scrolled with the rest of the screen
+        self.draw_header(&mut qc)?;
+        self.draw_footer(&mut qc)?;
         Ok(())
     }
 
@@ -374,24 +527,43 @@ impl<'a> TextPad<'a> {
     }
 
     fn last_visible_comp(&self) -> &Component {
-        let term_heigth = self.geo.borrow().term_height;
         self.components
-            .get_first_up_to(self.first + term_heigth, false)
+            .get_first_up_to(self.first + self.height(), false)
             .unwrap_or(self.components.last())
     }
 }
 
+/// The number of characters a line takes up on the screen (without the escape sequences)
+fn visible_len(line: &str) -> usize {
+    let mut len = 0;
+    let mut in_escape = false;
+    for c in line.chars() {
+        match (in_escape, c) {
+            (false, '\x1b') => in_escape = true,
+            (false, _) => len += 1,
+            (true, 'A'..='Z' | 'a'..='z') => in_escape = false,
+            (true, _) => {}
+        }
+    }
+    len
+}
+
 fn wrap_text(text: &str, width: usize) -> Vec<String> {
     let mut res = Vec::new();
     let mut buf = String::new();
+    // In chars, Cyrillic letters take two bytes
+    let mut buf_len = 0;
     for word in text.split(" ") {
-        if buf.len() + word.len() >= width {
+        let word_len = word.chars().count();
+        if buf_len + word_len >= width {
             buf.pop();
             res.push(buf.clone());
             buf.clear();
+            buf_len = 0;
         }
         buf.push_str(word);
         buf.push(' ');
+        buf_len += word_len + 1;
     }
     res.push(buf.trim_end().to_string());
     res
@@ -401,16 +573,23 @@ pub trait Buildable {
     fn build(text: &str, width: usize) -> Vec<String>;
 }
 
-// BUG: Doesn't display utf-8 properly
 // FIXME: Breaks make turn into \n which aren't accounted for
+fn wrap_paragraph(text: &str, width: usize) -> Vec<String> {
+    const IDENT: usize = 4;
+    let mut res = vec![String::new()];
+    let text = " ".repeat(IDENT) + text.trim();
+    res.append(&mut wrap_text(&text, width));
+    res
+}
+
+fn style_lines(lines: Vec<String>, style: theme::Style) -> Vec<String> {
+    lines.iter().map(|line| style.apply(line)).collect()
+}
+
 pub struct Paragraph;
 impl Buildable for Paragraph {
     fn build(text: &str, width: usize) -> Vec<String> {
-        const IDENT: usize = 4;
-        let mut res = vec![String::new()];
-        let text = " ".repeat(IDENT) + text.trim();
-        res.append(&mut wrap_text(&text, width));
-        res
+        style_lines(wrap_paragraph(text, width), config::get().theme.paragraph)
     }
 }
 
@@ -420,10 +599,9 @@ impl Buildable for Title {
         let mut res = vec![String::new()];
         let wraped_text = wrap_text(text.trim(), width);
         let wraped_text = wraped_text.iter().map(|line| {
-            let ident = (width - line.len()) / 2;
+            let ident = width.saturating_sub(line.chars().count()) / 2;
             let ident = " ".repeat(ident);
-            // TODO: Style this better
-            format!("{ident}{}{ident}", line.clone().on_dark_grey().bold())
+            format!("{ident}{}{ident}", config::get().theme.title.apply(line))
         });
         res.extend(wraped_text);
         res
@@ -433,10 +611,7 @@ impl Buildable for Title {
 pub struct Lead;
 impl Buildable for Lead {
     fn build(text: &str, width: usize) -> Vec<String> {
-        Paragraph::build(text, width)
-            .iter()
-            .map(|line| line.clone().bold().to_string())
-            .collect()
+        style_lines(wrap_paragraph(text, width), config::get().theme.lead)
     }
 }
 
@@ -444,11 +619,10 @@ pub struct Subtitle;
 impl Buildable for Subtitle {
     fn build(text: &str, width: usize) -> Vec<String> {
         let mut res = vec![String::new()];
-        res.extend(
-            Paragraph::build(text, width)
-                .iter()
-                .map(|line| line.clone().bold().to_string()),
-        );
+        res.extend(style_lines(
+            wrap_paragraph(text, width),
+            config::get().theme.subtitle,
+        ));
         res
     }
 }
@@ -460,17 +634,19 @@ impl Buildable for Boxed {
         res.push(format!(" ┌{}┐ ", "─".repeat(width - 4)));
         let mut text = text
             .split("\n")
-            .flat_map(|p| Paragraph::build(p, width - 6))
+            .flat_map(|p| wrap_paragraph(p, width - 6))
             .map(|row| format!(" │ {row}{} │ ", " ".repeat(width - 6 - row.chars().count())));
         text.next();
         res.extend(text);
         res.push(format!(" └{}┘ ", "─".repeat(width - 4)));
+        // Styled with the theme by the component, the feed items are styled by their state instead
         res
     }
 }
 
 enum FeedItemColor {
     Read,
+    Unread,
     New,
     NotNew,
     Selected,
@@ -479,23 +655,18 @@ enum FeedItemColor {
 
 impl FeedItemColor {
     fn to_style(&self, prev_style: Option<ContentStyle>) -> Option<ContentStyle> {
+        let theme = &config::get().theme;
+        let unread = theme.unread.to_content_style();
         match self {
-            FeedItemColor::Read => Some(ContentStyle::new().dim()),
-            FeedItemColor::New => Some(ContentStyle::new().blue()),
+            FeedItemColor::Read => Some(theme.read.to_content_style()),
+            FeedItemColor::Unread => Some(unread),
+            FeedItemColor::New => Some(theme.new.to_content_style()),
             FeedItemColor::NotNew => {
-                if prev_style.and_then(|s| s.foreground_color) == Some(Color::Blue) {
-                    Some(ContentStyle::new())
-                } else {
-                    None
-                }
+                (prev_style == Some(theme.new.to_content_style())).then_some(unread)
             }
-            FeedItemColor::Selected => Some(ContentStyle::new().red()),
+            FeedItemColor::Selected => Some(theme.selected.to_content_style()),
             FeedItemColor::NotSelected => {
-                if prev_style.and_then(|s| s.foreground_color) == Some(Color::Red) {
-                    Some(ContentStyle::new())
-                } else {
-                    None
-                }
+                (prev_style == Some(theme.selected.to_content_style())).then_some(unread)
             }
         }
     }
@@ -518,13 +689,107 @@ impl FeedItemColor {
 
 // TODO: Do a custom impl Buildable for FeedItem
 impl FeedItem {
-    pub fn build(&self) -> ComponentKind {
+    pub fn build(&self, is_saved: bool, snippet: Option<&str>) -> ComponentKind {
+        self.build_with(is_saved, snippet, Vec::new())
+    }
+
+    /// Builds the feed card with extra rows at the bottom
+    fn build_with(
+        &self,
+        is_saved: bool,
+        snippet: Option<&str>,
+        extra: Vec<String>,
+    ) -> ComponentKind {
         let mut rows = vec![self.title.clone()];
-        rows.push(self.published.to_string());
+        if let Some(snippet) = snippet {
+            rows.push(snippet.into());
+        }
+        let mut info = self.published.to_string();
+        if config::get().offline_mode && self.is_offline() {
+            info.push_str(" [offline]");
+        }
+        if is_saved {
+            info.push_str(" [saved]");
+        }
+        if !self.categories.is_empty() {
+            info.push_str(" | ");
+            info.push_str(&self.categories.join(", "));
+        }
+        rows.push(info);
+        rows.extend(extra);
         ComponentKind::Boxed(rows)
     }
 }
 
+impl Feed {
+    pub fn build_shown(
+        &self,
+    ) -> impl DoubleEndedIterator<Item = ComponentKind> + ExactSizeIterator {
+        self.shown.iter().map(|&index| self.build_item(index))
+    }
+
+    /// Builds the item, with the sources of its story if it has more versions
+    pub fn build_item(&self, index: usize) -> ComponentKind {
+        let item = &self.items[index];
+        let mut extra = Vec::new();
+        if let Some((sources, is_expanded)) = self.cluster_sources(index) {
+            let badges = sources
+                .iter()
+                .map(|source| format!("[{source}]"))
+                .collect::<Vec<_>>()
+                .join(" ");
+            let hint = config::get()
+                .keymap
+                .key(Action::ToggleStory, View::Feed)
+                .map(|key| match is_expanded {
+                    true => format!(" ({key} to collapse)"),
+                    false => format!(" ({key} for all versions)"),
+                })
+                .unwrap_or_default();
+            extra.push(format!("{badges}{hint}"));
+        } else if self.is_expanded_version(index) {
+            extra.push("[another version]".into());
+        }
+        item.build_with(self.is_saved(item), self.snippet(item), extra)
+    }
+
+    /// Lists the news sites with the number that toggles them, crossing out the hidden ones
+    pub fn build_header(&self) -> Option<String> {
+        let filter = self.filter();
+        if !filter.is_active() {
+            return None;
+        }
+        let mut header = backend::Registry::get()
+            .enabled()
+            .iter()
+            .take(9)
+            .enumerate()
+            .map(|(index, site)| {
+                let name = site.to_string();
+                let label = format!("{}:{name}", index + 1);
+                if filter.hidden_sources.contains(&name) {
+                    config::get().theme.hidden_source.apply(&label)
+                } else {
+                    label
+                }
+            })
+            .collect::<Vec<_>>()
+            .join(" ");
+        if let Some(topic) = filter.topic {
+            header.push_str(&format!(" | {topic}"));
+        }
+        if filter.unread_only {
+            header.push_str(" | unread only");
+        }
+        Some(header)
+    }
+}
+
+fn spinner_frame(started: Instant) -> char {
+    const FRAMES: [char; 10] = ['⠋', '⠙', '⠹', '⠸', '⠼', '⠴', '⠦', '⠧', '⠇', '⠏'];
+    FRAMES[(started.elapsed().as_millis() / 100) as usize % FRAMES.len()]
+}
+
 // TODO: Add the List component
 
 impl ErrorWindow<'_> {
@@ -534,13 +799,13 @@ impl ErrorWindow<'_> {
         let component = ComponentKind::Boxed(component);
         let mut component = Component::new(component);
         component.build(geo.width as usize, 0);
-        let lines = component.content(Some(ContentStyle::new().red()));
+        let lines = component.content(Some(config::get().theme.error.to_content_style()));
 
         let starty = (geo.term_height - lines.len() as u16) / 2;
         qc.queue(terminal::Clear(terminal::ClearType::All))?
             .queue(cursor::MoveTo(geo.startx, starty))?;
         for line in lines {
-            qc.queue(style::Print(line.red()))?
+            qc.queue(style::Print(line))?
                 .queue(cursor::MoveDown(1))?
                 .queue(cursor::MoveToColumn(geo.startx))?;
         }
@@ -551,3 +816,17 @@ impl ErrorWindow<'_> {
         self.geo.borrow_mut().resize(term_dimens);
     }
 }
+
+impl LoadingWindow<'_> {
+    pub fn draw(&self, mut qc: impl QueueableCommand + Write) -> io::Result<()> {
+        const TEXT: &str = "LOADING...";
+        let geo = self.geo.borrow();
+        let text = format!("{} {TEXT}", spinner_frame(self.started));
+        let x = geo.startx + geo.width.saturating_sub(text.chars().count() as u16) / 2;
+        qc.queue(cursor::MoveTo(0, geo.term_height / 2))?
+            .queue(terminal::Clear(terminal::ClearType::CurrentLine))?
+            .queue(cursor::MoveToColumn(x))?
+            .queue(style::Print(text))?;
+        Ok(())
+    }
+}
@@ -0,0 +1,345 @@
+use crate::config::Script;
+
+use super::ComponentKind;
+
+/// Words where nj and dž are two letters, not one
+const SPLIT_DIGRAPHS: &[&str] = &["injek", "konjug", "konjunk", "nadž", "podž", "tanjug"];
+
+pub(super) fn to_latin(c: char) -> Option<&'static str> {
+    let latin = match c {
+        'а' => "a",
+        'б' => "b",
+        'в' => "v",
+        'г' => "g",
+        'д' => "d",
+        'ђ' => "đ",
+        'е' => "e",
+        'ж' => "ž",
+        'з' => "z",
+        'и' => "i",
+        'ј' => "j",
+        'к' => "k",
+        'л' => "l",
+        'љ' => "lj",
+        'м' => "m",
+        'н' => "n",
+        'њ' => "nj",
+        'о' => "o",
+        'п' => "p",
+        'р' => "r",
+        'с' => "s",
+        'т' => "t",
+        'ћ' => "ć",
+        'у' => "u",
+        'ф' => "f",
+        'х' => "h",
+        'ц' => "c",
+        'ч' => "č",
+        'џ' => "dž",
+        'ш' => "š",
+        'А' => "A",
+        'Б' => "B",
+        'В' => "V",
+        'Г' => "G",
+        'Д' => "D",
+        'Ђ' => "Đ",
+        'Е' => "E",
+        'Ж' => "Ž",
+        'З' => "Z",
+        'И' => "I",
+        'Ј' => "J",
+        'К' => "K",
+        'Л' => "L",
+        'Љ' => "Lj",
+        'М' => "M",
+        'Н' => "N",
+        'Њ' => "Nj",
+        'О' => "O",
+        'П' => "P",
+        'Р' => "R",
+        'С' => "S",
+        'Т' => "T",
+        'Ћ' => "Ć",
+        'У' => "U",
+        'Ф' => "F",
+        'Х' => "H",
+        'Ц' => "C",
+        'Ч' => "Č",
+        'Џ' => "Dž",
+        'Ш' => "Š",
+        _ => return None,
+    };
+    Some(latin)
+}
+
+fn to_cyrillic(c: char) -> Option<char> {
+    let cyrillic = match c {
+        'a' => 'а',
+        'b' => 'б',
+        'c' => 'ц',
+        'č' => 'ч',
+        'ć' => 'ћ',
+        'd' => 'д',
+        'đ' => 'ђ',
+        'e' => 'е',
+        'f' => 'ф',
+        'g' => 'г',
+        'h' => 'х',
+        'i' => 'и',
+        'j' => 'ј',
+        'k' => 'к',
+        'l' => 'л',
+        'm' => 'м',
+        'n' => 'н',
+        'o' => 'о',
+        'p' => 'п',
+        'r' => 'р',
+        's' => 'с',
+        'š' => 'ш',
+        't' => 'т',
+        'u' => 'у',
+        'v' => 'в',
+        'z' => 'з',
+        'ž' => 'ж',
+        'A' => 'А',
+        'B' => 'Б',
+        'C' => 'Ц',
+        'Č' => 'Ч',
+        'Ć' => 'Ћ',
+        'D' => 'Д',
+        'Đ' => 'Ђ',
+        'E' => 'Е',
+        'F' => 'Ф',
+        'G' => 'Г',
+        'H' => 'Х',
+        'I' => 'И',
+        'J' => 'Ј',
+        'K' => 'К',
+        'L' => 'Л',
+        'M' => 'М',
+        'N' => 'Н',
+        'O' => 'О',
+        'P' => 'П',
+        'R' => 'Р',
+        'S' => 'С',
+        'Š' => 'Ш',
+        'T' => 'Т',
+        'U' => 'У',
+        'V' => 'В',
+        'Z' => 'З',
+        'Ž' => 'Ж',
+        _ => return None,
+    };
+    Some(cyrillic)
+}
+
+fn digraph_to_cyrillic(first: char, second: char) -> Option<char> {
+    let cyrillic = match (first, second) {
+        ('l', 'j') => 'љ',
+        ('n', 'j') => 'њ',
+        ('d', 'ž') => 'џ',
+        ('L', 'j' | 'J') => 'Љ',
+        ('N', 'j' | 'J') => 'Њ',
+        ('D', 'ž' | 'Ž') => 'Џ',
+        _ => return None,
+    };
+    Some(cyrillic)
+}
+
+fn word_to_latin(word: &str, latin: &mut String) {
+    let chars = word.chars().collect::<Vec<_>>();
+    for (index, &c) in chars.iter().enumerate() {
+        let Some(letters) = to_latin(c) else {
+            latin.push(c);
+            continue;
+        };
+        // Lj, Nj and Dž are all caps in an all caps word (ЉУБАВ -> LJUBAV)
+        let is_all_caps = chars
+            .get(index + 1)
+            .or(index.checked_sub(1).and_then(|prev| chars.get(prev)))
+            .is_some_and(|c| c.is_uppercase());
+        if letters.len() > 1 && is_all_caps {
+            latin.push_str(&letters.to_uppercase());
+        } else {
+            latin.push_str(letters);
+        }
+    }
+}
+
+/// Links, emails and foreign words (with letters that Serbian Latin doesn't have) stay the same
+fn is_foreign(word: &str) -> bool {
+    let is_domain = word
+        .char_indices()
+        .any(|(pos, c)| c == '.' && pos > 0 && word[pos + 1..].starts_with(char::is_alphabetic));
+    word.contains("://")
+        || word.contains('@')
+        || is_domain
+        || word.contains(['q', 'w', 'x', 'y', 'Q', 'W', 'X', 'Y'])
+}
+
+fn word_to_cyrillic(word: &str, cyrillic: &mut String) {
+    if is_foreign(word) {
+        cyrillic.push_str(word);
+        return;
+    }
+    let chars = word.chars().collect::<Vec<_>>();
+    let lowercase = word.to_lowercase().chars().collect::<Vec<_>>();
+    let mut split = vec![false; chars.len()];
+    if lowercase.len() == chars.len() {
+        for exception in SPLIT_DIGRAPHS {
+            let exception = exception.chars().collect::<Vec<_>>();
+            for start in 0..chars.len().saturating_sub(exception.len() - 1) {
+                if lowercase[start..].starts_with(&exception) {
+                    split[start..start + exception.len()].fill(true);
+                }
+            }
+        }
+    }
+
+    let mut index = 0;
+    while index < chars.len() {
+        let c = chars[index];
+        if let Some(&next) = chars.get(index + 1)
+            && !split[index]
+            && let Some(digraph) = digraph_to_cyrillic(c, next)
+        {
+            cyrillic.push(digraph);
+            index += 2;
+            continue;
+        }
+        cyrillic.push(to_cyrillic(c).unwrap_or(c));
+        index += 1;
+    }
+}
+
+/// Changes the text to the script. Text in square brackets (like the source badges and
+/// [offline]) is left as is.
+pub fn transliterate(text: &str, script: Script) -> String {
+    let convert = match script {
+        Script::AsPublished => return text.into(),
+        Script::Latin => word_to_latin,
+        Script::Cyrillic => word_to_cyrillic,
+    };
+    let mut converted = String::with_capacity(text.len());
+    let mut in_brackets = false;
+    let mut pos = 0;
+    for word in text.split_inclusive(char::is_whitespace) {
+        // A bracket that is never closed is just a bracket
+        if word.starts_with('[') && text[pos..].contains(']') {
+            in_brackets = true;
+        }
+        pos += word.len();
+        if in_brackets {
+            converted.push_str(word);
+        } else {
+            convert(word, &mut converted);
+        }
+        if word.trim_end().ends_with(']') {
+            in_brackets = false;
+        }
+    }
+    converted
+}
+
+impl ComponentKind {
+    pub fn transliterated(self, script: Script) -> Self {
+        match self {
+            ComponentKind::Title(text) => ComponentKind::Title(transliterate(&text, script)),
+            ComponentKind::Subtitle(text) => ComponentKind::Subtitle(transliterate(&text, script)),
+            ComponentKind::Lead(text) => ComponentKind::Lead(transliterate(&text, script)),
+            ComponentKind::Paragraph(text) => {
+                ComponentKind::Paragraph(transliterate(&text, script))
+            }
+            ComponentKind::Boxed(rows) => {
+                ComponentKind::Boxed(rows.iter().map(|row| transliterate(row, script)).collect())
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::frontend::search;
+
+    #[test]
+    fn to_cyrillic() {
+        let cases = [
+            ("ljubav i njiva", "љубав и њива"),
+            ("Ljubljana", "Љубљана"),
+            ("LJUBAV", "ЉУБАВ"),
+            ("Džemper", "Џемпер"),
+            // Split digraphs
+            ("injekcija", "инјекција"),
+            ("konjunktura", "конјунктура"),
+            ("nadživeti", "надживети"),
+            ("Tanjug", "Танјуг"),
+            // Foreign words, links and emails
+            ("New York", "New York"),
+            ("https://n1info.rs", "https://n1info.rs"),
+            ("n1info.rs", "n1info.rs"),
+            ("ime@mail.com", "ime@mail.com"),
+            // Brackets
+            ("[N1] vesti", "[N1] вести"),
+            ("[offline] [saved] vesti", "[offline] [saved] вести"),
+            ("[nezatvoreno vesti", "[незатворено вести"),
+        ];
+        for (text, expected) in cases {
+            assert_eq!(transliterate(text, Script::Cyrillic), expected, "{text}");
+        }
+    }
+
+    #[test]
+    fn to_latin() {
+        let cases = [
+            ("љубав и њива", "ljubav i njiva"),
+            ("Љубљана", "Ljubljana"),
+            ("ЉУБАВ", "LJUBAV"),
+            ("Џемпер", "Džemper"),
+            ("ЏЕМПЕР", "DŽEMPER"),
+            ("[Δ] вести", "[Δ] vesti"),
+            ("[незатворено вести", "[nezatvoreno vesti"),
+        ];
+        for (text, expected) in cases {
+            assert_eq!(transliterate(text, Script::Latin), expected, "{text}");
+        }
+    }
+
+    #[test]
+    fn round_trip() {
+        let cases = [
+            "ljubav i njiva",
+            "Ljubljana",
+            "LJUBAV",
+            "Džemper",
+            "injekcija",
+        ];
+        for text in cases {
+            let cyrillic = transliterate(text, Script::Cyrillic);
+            assert_eq!(transliterate(&cyrillic, Script::Latin), text, "{text}");
+        }
+    }
+
+    #[test]
+    fn fold_digraphs() {
+        let cases = [
+            ("Љубав", "ljubav"),
+            ("ljubav", "љубав"),
+            ("Њива", "njiva"),
+            ("džemper", "Џемпер"),
+            ("Šabac", "sabac"),
+            ("Шабац", "sabac"),
+        ];
+        for (text, query) in cases {
+            assert!(search::contains(text, query), "{text} {query}");
+        }
+    }
+
+    #[test]
+    fn highlight_inside_digraph() {
+        // Both "jl" matches in the folded "jljl" contain the љ
+        assert_eq!(search::count("jљl", "jl"), 1);
+        assert_eq!(search::highlight("jљl", "jl"), "\x1b[7mjљ\x1b[27ml");
+        assert_eq!(search::count("љуљ", "lj"), 2);
+    }
+}
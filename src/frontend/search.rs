@@ -0,0 +1,208 @@
+use std::iter;
+
+use crossterm::{
+    event::{KeyCode, KeyEvent, KeyEventKind},
+    style::{Attribute, Stylize},
+};
+
+use super::script;
+
+/// Lowercases the character and strips its diacritics, so that "sabac" matches "Šabac" (and
+/// "Шабац"). Cyrillic letters become their Latin counterparts, so љ, њ and џ become two letters,
+/// like they are in Latin.
+pub fn fold(c: char) -> impl Iterator<Item = char> {
+    let c = c.to_lowercase().next().unwrap_or(c);
+    let (first, second) = match script::to_latin(c) {
+        Some(latin) => {
+            let mut letters = latin.chars();
+            (letters.next().unwrap_or(c), letters.next())
+        }
+        None => (c, None),
+    };
+    iter::once(strip_diacritics(first)).chain(second.map(strip_diacritics))
+}
+
+fn strip_diacritics(c: char) -> char {
+    match c {
+        'š' => 's',
+        'č' | 'ć' | 'ç' => 'c',
+        'ž' => 'z',
+        'đ' => 'd',
+        'à' | 'á' | 'â' | 'ã' | 'ä' | 'å' => 'a',
+        'è' | 'é' | 'ê' | 'ë' => 'e',
+        'ì' | 'í' | 'î' | 'ï' => 'i',
+        'ò' | 'ó' | 'ô' | 'õ' | 'ö' => 'o',
+        'ù' | 'ú' | 'û' | 'ü' => 'u',
+        'ñ' => 'n',
+        'ý' | 'ÿ' => 'y',
+        c => c,
+    }
+}
+
+fn fold_str(text: &str) -> Vec<char> {
+    text.chars().flat_map(fold).collect()
+}
+
+/// Finds where the query is in the (already folded) text, as ranges of char indexes
+fn find_folded(text: &[char], query: &[char]) -> Vec<(usize, usize)> {
+    let mut found = Vec::new();
+    if query.is_empty() {
+        return found;
+    }
+    let mut start = 0;
+    while start + query.len() <= text.len() {
+        if text[start..start + query.len()] == *query {
+            found.push((start, start + query.len()));
+            start += query.len();
+        } else {
+            start += 1;
+        }
+    }
+    found
+}
+
+pub fn contains(text: &str, query: &str) -> bool {
+    !find_folded(&fold_str(text), &fold_str(query)).is_empty()
+}
+
+/// The folded characters of a (possibly styled) line with their byte positions, without the
+/// escape sequences (both letters of a folded љ have its position)
+fn visible_chars(line: &str) -> Vec<(usize, char)> {
+    let mut visible = Vec::new();
+    let mut in_escape = false;
+    for (pos, c) in line.char_indices() {
+        match (in_escape, c) {
+            (false, '\x1b') => in_escape = true,
+            (false, _) => visible.extend(fold(c).map(|folded| (pos, folded))),
+            (true, 'A'..='Z' | 'a'..='z') => in_escape = false,
+            (true, _) => {}
+        }
+    }
+    visible
+}
+
+fn find_in_line(visible: &[(usize, char)], query: &str) -> Vec<(usize, usize)> {
+    let text = visible.iter().map(|(_, c)| *c).collect::<Vec<_>>();
+    let mut found = find_folded(&text, &fold_str(query));
+    // A match can start in the second letter of a folded љ that ends the previous one, like "jl"
+    // in "jљl", and the same char can't be highlighted twice
+    let mut last_pos = None;
+    found.retain(|&(start, end)| {
+        let overlaps = last_pos.is_some_and(|last| visible[start].0 <= last);
+        if !overlaps {
+            last_pos = Some(visible[end - 1].0);
+        }
+        !overlaps
+    });
+    found
+}
+
+/// Counts the matches in a (possibly styled) line
+pub fn count(line: &str, query: &str) -> usize {
+    find_in_line(&visible_chars(line), query).len()
+}
+
+/// Shows the matches in reverse video. The line can be styled already, the escape sequences
+/// are skipped while matching.
+pub fn highlight(line: &str, query: &str) -> String {
+    let visible = visible_chars(line);
+    let found = find_in_line(&visible, query);
+    if found.is_empty() {
+        return line.into();
+    }
+
+    let mut highlighted = String::with_capacity(line.len() + found.len() * 10);
+    let mut last = 0;
+    for (start, end) in found {
+        let start = visible[start].0;
+        // The match can end in the middle of a folded љ, so it ends after the char of its last
+        // folded letter
+        let last_pos = visible[end - 1].0;
+        let end = last_pos + line[last_pos..].chars().next().map_or(0, char::len_utf8);
+        highlighted.push_str(&line[last..start]);
+        highlighted.push_str(&Attribute::Reverse.to_string());
+        highlighted.push_str(&line[start..end]);
+        highlighted.push_str(&Attribute::NoReverse.to_string());
+        last = end;
+    }
+    highlighted.push_str(&line[last..]);
+    highlighted
+}
+
+pub enum PromptEvent {
+    Changed,
+    Confirmed,
+    Cancelled,
+}
+
+/// The last search and the prompt for typing a new one
+pub struct Search {
+    query: String,
+    typing: bool,
+    /// Where the view was when the search started
+    origin: usize,
+}
+
+impl Search {
+    pub fn new() -> Self {
+        Self {
+            query: String::new(),
+            typing: false,
+            origin: 0,
+        }
+    }
+
+    pub fn query(&self) -> Option<&str> {
+        if self.query.is_empty() {
+            None
+        } else {
+            Some(&self.query)
+        }
+    }
+
+    pub fn is_typing(&self) -> bool {
+        self.typing
+    }
+
+    pub fn origin(&self) -> usize {
+        self.origin
+    }
+
+    pub fn start(&mut self, origin: usize) {
+        self.query.clear();
+        self.typing = true;
+        self.origin = origin;
+    }
+
+    pub fn handle_key(&mut self, event: KeyEvent) -> Option<PromptEvent> {
+        if event.kind != KeyEventKind::Press {
+            return None;
+        }
+        match event.code {
+            KeyCode::Char(c) => {
+                self.query.push(c);
+                Some(PromptEvent::Changed)
+            }
+            KeyCode::Backspace if self.query.pop().is_some() => Some(PromptEvent::Changed),
+            KeyCode::Enter => {
+                self.typing = false;
+                Some(PromptEvent::Confirmed)
+            }
+            KeyCode::Backspace | KeyCode::Esc => {
+                self.clear();
+                Some(PromptEvent::Cancelled)
+            }
+            _ => None,
+        }
+    }
+
+    pub fn clear(&mut self) {
+        self.query.clear();
+        self.typing = false;
+    }
+
+    /// The query, followed by info (like the number of matches), to show below the view
+    pub fn prompt(&self, info: &str) -> String {
+        format!("/{} {}", self.query, info.dim())
+    }
+}
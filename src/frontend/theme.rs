@@ -0,0 +1,290 @@
+use std::collections::HashMap;
+
+use crossterm::style::{Attribute, Color, ContentStyle, Stylize};
+use serde::{Deserialize, Deserializer};
+
+#[derive(Deserialize, Default, Clone, Copy, PartialEq)]
+#[serde(default, deny_unknown_fields)]
+pub struct Style {
+    #[serde(deserialize_with = "deserialize_color")]
+    fg: Option<Color>,
+    #[serde(deserialize_with = "deserialize_color")]
+    bg: Option<Color>,
+    bold: bool,
+    dim: bool,
+    italic: bool,
+    underlined: bool,
+    reverse: bool,
+    crossed_out: bool,
+}
+
+fn deserialize_color<'de, D>(deserializer: D) -> Result<Option<Color>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let name = String::deserialize(deserializer)?;
+    Color::try_from(name.as_str()).map(Some).map_err(|_| {
+        serde::de::Error::custom(format!(
+            "unknown color \"{name}\" (use black, red, green, yellow, blue, magenta, cyan, white, \
+             grey or their dark_ variants)"
+        ))
+    })
+}
+
+impl Style {
+    const fn new() -> Self {
+        Self {
+            fg: None,
+            bg: None,
+            bold: false,
+            dim: false,
+            italic: false,
+            underlined: false,
+            reverse: false,
+            crossed_out: false,
+        }
+    }
+
+    const fn fg(self, color: Color) -> Self {
+        Self {
+            fg: Some(color),
+            ..self
+        }
+    }
+
+    const fn bg(self, color: Color) -> Self {
+        Self {
+            bg: Some(color),
+            ..self
+        }
+    }
+
+    const fn bold(self) -> Self {
+        Self { bold: true, ..self }
+    }
+
+    const fn dim(self) -> Self {
+        Self { dim: true, ..self }
+    }
+
+    const fn underlined(self) -> Self {
+        Self {
+            underlined: true,
+            ..self
+        }
+    }
+
+    const fn reverse(self) -> Self {
+        Self {
+            reverse: true,
+            ..self
+        }
+    }
+
+    const fn crossed_out(self) -> Self {
+        Self {
+            crossed_out: true,
+            ..self
+        }
+    }
+
+    pub fn to_content_style(self) -> ContentStyle {
+        let mut style = ContentStyle::new();
+        style.foreground_color = self.fg;
+        style.background_color = self.bg;
+        for (is_set, attribute) in [
+            (self.bold, Attribute::Bold),
+            (self.dim, Attribute::Dim),
+            (self.italic, Attribute::Italic),
+            (self.underlined, Attribute::Underlined),
+            (self.reverse, Attribute::Reverse),
+            (self.crossed_out, Attribute::CrossedOut),
+        ] {
+            if is_set {
+                style = style.attribute(attribute);
+            }
+        }
+        style
+    }
+
+    pub fn apply(self, text: &str) -> String {
+        self.to_content_style().apply(text).to_string()
+    }
+}
+
+/// The styles of the components and of the states of the feed items
+#[derive(Clone, Copy)]
+pub struct Theme {
+    pub title: Style,
+    pub subtitle: Style,
+    pub lead: Style,
+    pub paragraph: Style,
+    pub boxed: Style,
+    pub error: Style,
+    pub read: Style,
+    pub unread: Style,
+    pub new: Style,
+    pub selected: Style,
+    /// The sources hidden by the filter in the header
+    pub hidden_source: Style,
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Self::DEFAULT
+    }
+}
+
+/// A theme from the config file, which changes some of the styles of a built-in theme
+#[derive(Deserialize)]
+pub struct UserTheme {
+    base: Option<String>,
+    #[serde(flatten)]
+    styles: HashMap<String, Style>,
+}
+
+/// The `[colors]` section of the older config files, which only set the colors of the feed items
+#[derive(Deserialize, Default)]
+#[serde(default, deny_unknown_fields)]
+pub struct LegacyColors {
+    #[serde(deserialize_with = "deserialize_color")]
+    new: Option<Color>,
+    #[serde(deserialize_with = "deserialize_color")]
+    selected: Option<Color>,
+    /// Read items stayed dimmed if this wasn't set
+    #[serde(deserialize_with = "deserialize_color")]
+    read: Option<Color>,
+}
+
+impl Theme {
+    const DEFAULT: Self = Self {
+        title: Style::new().bg(Color::DarkGrey).bold(),
+        subtitle: Style::new().bold(),
+        lead: Style::new().bold(),
+        paragraph: Style::new(),
+        boxed: Style::new(),
+        error: Style::new().fg(Color::Red),
+        read: Style::new().dim(),
+        unread: Style::new(),
+        new: Style::new().fg(Color::Blue),
+        selected: Style::new().fg(Color::Red),
+        hidden_source: Style::new().dim().crossed_out(),
+    };
+
+    const LIGHT_TERMINAL: Self = Self {
+        title: Style::new().fg(Color::White).bg(Color::DarkBlue).bold(),
+        error: Style::new().fg(Color::DarkRed),
+        new: Style::new().fg(Color::DarkBlue),
+        selected: Style::new().fg(Color::DarkRed),
+        ..Self::DEFAULT
+    };
+
+    const HIGH_CONTRAST: Self = Self {
+        title: Style::new().fg(Color::Black).bg(Color::White).bold(),
+        lead: Style::new().fg(Color::White).bold(),
+        subtitle: Style::new().fg(Color::White).bold().underlined(),
+        error: Style::new().fg(Color::White).bg(Color::Red).bold(),
+        read: Style::new().fg(Color::Grey),
+        unread: Style::new().fg(Color::White),
+        new: Style::new().fg(Color::Cyan).bold(),
+        selected: Style::new().fg(Color::Yellow).bold(),
+        ..Self::DEFAULT
+    };
+
+    const MONOCHROME: Self = Self {
+        title: Style::new().reverse().bold(),
+        error: Style::new().bold(),
+        new: Style::new().bold(),
+        selected: Style::new().reverse(),
+        ..Self::DEFAULT
+    };
+
+    const BUILT_IN: [(&str, Self); 4] = [
+        ("default", Self::DEFAULT),
+        ("light-terminal", Self::LIGHT_TERMINAL),
+        ("high-contrast", Self::HIGH_CONTRAST),
+        ("monochrome", Self::MONOCHROME),
+    ];
+
+    fn built_in(name: &str) -> Option<Self> {
+        Self::BUILT_IN
+            .iter()
+            .find(|(built_in, _)| *built_in == name)
+            .map(|(_, theme)| *theme)
+    }
+
+    fn style_mut(&mut self, name: &str) -> Option<&mut Style> {
+        let style = match name {
+            "title" => &mut self.title,
+            "subtitle" => &mut self.subtitle,
+            "lead" => &mut self.lead,
+            "paragraph" => &mut self.paragraph,
+            "boxed" => &mut self.boxed,
+            "error" => &mut self.error,
+            "read" => &mut self.read,
+            "unread" => &mut self.unread,
+            "new" => &mut self.new,
+            "selected" => &mut self.selected,
+            "hidden_source" => &mut self.hidden_source,
+            _ => return None,
+        };
+        Some(style)
+    }
+
+    /// Finds the theme among the built-in and the user themes, and colors its feed items with the
+    /// legacy colors
+    pub fn resolve(
+        name: &str,
+        user_themes: &HashMap<String, UserTheme>,
+        colors: Option<&LegacyColors>,
+    ) -> Result<Self, String> {
+        let mut theme = match user_themes.get(name) {
+            Some(user_theme) => user_theme.resolve(name)?,
+            None => Self::built_in(name).ok_or_else(|| {
+                let mut names = Self::BUILT_IN.map(|(name, _)| name.to_string()).to_vec();
+                names.extend(user_themes.keys().cloned());
+                format!(
+                    "unknown theme \"{name}\" (the themes are {})",
+                    names.join(", ")
+                )
+            })?,
+        };
+        if let Some(colors) = colors {
+            theme.apply_legacy_colors(colors);
+        }
+        // The feed items are restyled by comparing their styles with these
+        if theme.new == theme.selected || theme.read == theme.new || theme.read == theme.selected {
+            return Err(format!(
+                "the new, selected and read styles of the theme \"{name}\" have to be different"
+            ));
+        }
+        Ok(theme)
+    }
+
+    fn apply_legacy_colors(&mut self, colors: &LegacyColors) {
+        if let Some(color) = colors.new {
+            self.new = self.new.fg(color);
+        }
+        if let Some(color) = colors.selected {
+            self.selected = self.selected.fg(color);
+        }
+        if let Some(color) = colors.read {
+            self.read = Style::new().fg(color);
+        }
+    }
+}
+
+impl UserTheme {
+    fn resolve(&self, name: &str) -> Result<Theme, String> {
+        let base = self.base.as_deref().unwrap_or("default");
+        let mut theme = Theme::built_in(base).ok_or_else(|| {
+            format!("the theme \"{name}\" is based on \"{base}\", which isn't a built-in theme")
+        })?;
+        for (element, style) in &self.styles {
+            *theme.style_mut(element).ok_or_else(|| {
+                format!("unknown element \"{element}\" in the theme \"{name}\"")
+            })? = *style;
+        }
+        Ok(theme)
+    }
+}
@@ -1,4 +1,9 @@
+use std::{collections::HashMap, fmt::Display};
+
 use crossterm::event::{Event, KeyCode, KeyEventKind, KeyModifiers, MouseButton, MouseEventKind};
+use serde::Deserialize;
+
+use crate::config;
 
 #[derive(Clone, Copy)]
 pub enum Direction {
@@ -16,78 +21,380 @@ pub enum Controls {
     MouseSelect(u16, u16),
     GotoTop,
     Refresh,
+    MarkAllRead,
+    MarkUnread,
+    ToggleUnreadOnly,
+    ToggleSource(usize),
+    NextTopic,
+    Search,
+    NextMatch(Direction),
+    ClearSearch,
+    SearchArticles,
+    ToggleStory,
+    Save,
+    OpenSaved,
+    Help,
 }
 
-#[derive(Clone, Copy)]
+#[derive(Clone, Copy, PartialEq)]
 pub enum View {
     Feed,
     Article,
     Error,
+    /// The loading window, which can only be cancelled
+    Loading,
+}
+
+/// What a key can be bound to in the config file. Some actions do different things in different
+/// views (up moves the selection in the feed and scrolls the article).
+#[derive(Clone, Copy, PartialEq)]
+pub enum Action {
+    Quit,
+    Up,
+    Down,
+    Select,
+    Top,
+    Refresh,
+    MarkAllRead,
+    MarkUnread,
+    UnreadOnly,
+    ToggleSource(usize),
+    NextTopic,
+    Search,
+    NextMatch,
+    PreviousMatch,
+    ClearSearch,
+    SearchArticles,
+    ToggleStory,
+    Save,
+    OpenSaved,
+    Help,
+}
+
+impl Action {
+    const NAMES: [(&str, Action); 28] = [
+        ("quit", Action::Quit),
+        ("up", Action::Up),
+        ("down", Action::Down),
+        ("select", Action::Select),
+        ("top", Action::Top),
+        ("refresh", Action::Refresh),
+        ("mark-all-read", Action::MarkAllRead),
+        ("mark-unread", Action::MarkUnread),
+        ("unread-only", Action::UnreadOnly),
+        ("toggle-source-1", Action::ToggleSource(0)),
+        ("toggle-source-2", Action::ToggleSource(1)),
+        ("toggle-source-3", Action::ToggleSource(2)),
+        ("toggle-source-4", Action::ToggleSource(3)),
+        ("toggle-source-5", Action::ToggleSource(4)),
+        ("toggle-source-6", Action::ToggleSource(5)),
+        ("toggle-source-7", Action::ToggleSource(6)),
+        ("toggle-source-8", Action::ToggleSource(7)),
+        ("toggle-source-9", Action::ToggleSource(8)),
+        ("next-topic", Action::NextTopic),
+        ("search", Action::Search),
+        ("next-match", Action::NextMatch),
+        ("previous-match", Action::PreviousMatch),
+        ("clear-search", Action::ClearSearch),
+        ("search-articles", Action::SearchArticles),
+        ("toggle-story", Action::ToggleStory),
+        ("save", Action::Save),
+        ("open-saved", Action::OpenSaved),
+        ("help", Action::Help),
+    ];
+
+    fn from_name(name: &str) -> Option<Self> {
+        Self::NAMES
+            .iter()
+            .find(|(action, _)| *action == name)
+            .map(|(_, action)| *action)
+    }
+
+    fn control(self, view: View) -> Option<Controls> {
+        let control = match (self, view) {
+            (Action::Quit, _) => Controls::Quit,
+            (Action::Up, View::Feed) => Controls::MoveSelect(Direction::Up),
+            (Action::Up, View::Article) => Controls::Scroll(Direction::Up, 1),
+            (Action::Down, View::Feed) => Controls::MoveSelect(Direction::Down),
+            (Action::Down, View::Article) => Controls::Scroll(Direction::Down, 1),
+            (Action::Select, View::Feed) => Controls::Select,
+            (Action::Top, View::Feed | View::Article) => Controls::GotoTop,
+            (Action::Refresh, View::Feed | View::Error) => Controls::Refresh,
+            (Action::MarkAllRead, View::Feed) => Controls::MarkAllRead,
+            (Action::MarkUnread, View::Feed) => Controls::MarkUnread,
+            (Action::UnreadOnly, View::Feed) => Controls::ToggleUnreadOnly,
+            (Action::ToggleSource(index), View::Feed) => Controls::ToggleSource(index),
+            (Action::NextTopic, View::Feed) => Controls::NextTopic,
+            (Action::Search, View::Feed | View::Article) => Controls::Search,
+            (Action::NextMatch, View::Feed | View::Article) => Controls::NextMatch(Direction::Down),
+            (Action::PreviousMatch, View::Feed | View::Article) => {
+                Controls::NextMatch(Direction::Up)
+            }
+            (Action::ClearSearch, View::Feed | View::Article) => Controls::ClearSearch,
+            (Action::SearchArticles, View::Feed) => Controls::SearchArticles,
+            (Action::ToggleStory, View::Feed) => Controls::ToggleStory,
+            (Action::Save, View::Feed | View::Article) => Controls::Save,
+            (Action::OpenSaved, View::Feed) => Controls::OpenSaved,
+            (Action::Help, _) => Controls::Help,
+            // TODO: Consider adding Controls::GotoBottom
+            _ => return None,
+        };
+        Some(control)
+    }
+
+    /// Whether the actions can be used in the same view
+    fn shares_view(self, other: Self) -> bool {
+        [View::Feed, View::Article, View::Error, View::Loading]
+            .into_iter()
+            .any(|view| self.control(view).is_some() && other.control(view).is_some())
+    }
+}
+
+impl Display for Action {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name = Self::NAMES
+            .iter()
+            .find(|(_, action)| action == self)
+            .map_or("", |(name, _)| name);
+        write!(f, "{name}")
+    }
+}
+
+const KEY_NAMES: [(&str, KeyCode); 13] = [
+    ("Enter", KeyCode::Enter),
+    ("Esc", KeyCode::Esc),
+    ("Backspace", KeyCode::Backspace),
+    ("Tab", KeyCode::Tab),
+    ("Space", KeyCode::Char(' ')),
+    ("Up", KeyCode::Up),
+    ("Down", KeyCode::Down),
+    ("Left", KeyCode::Left),
+    ("Right", KeyCode::Right),
+    ("PageUp", KeyCode::PageUp),
+    ("PageDown", KeyCode::PageDown),
+    ("Home", KeyCode::Home),
+    ("End", KeyCode::End),
+];
+
+/// Parses a key sequence, like "gg" or "<Enter>"
+fn parse_keys(keys: &str) -> Result<Vec<KeyCode>, String> {
+    let mut parsed = Vec::new();
+    let mut rest = keys;
+    while let Some(c) = rest.chars().next() {
+        if c == '<'
+            && let Some(end) = rest.find('>')
+            && end > 1
+        {
+            let name = &rest[1..end];
+            let (_, code) = KEY_NAMES
+                .iter()
+                .find(|(key, _)| key.eq_ignore_ascii_case(name))
+                .ok_or_else(|| {
+                    let names = KEY_NAMES.map(|(key, _)| format!("<{key}>"));
+                    format!("unknown key <{name}> (the keys are {})", names.join(", "))
+                })?;
+            parsed.push(*code);
+            rest = &rest[end + 1..];
+        } else {
+            parsed.push(KeyCode::Char(c));
+            rest = &rest[c.len_utf8()..];
+        }
+    }
+    if parsed.is_empty() {
+        return Err("empty key sequence".into());
+    }
+    Ok(parsed)
+}
+
+fn keys_to_string(keys: &[KeyCode]) -> String {
+    keys.iter()
+        .map(|key| match key {
+            KeyCode::Char(' ') => "<Space>".into(),
+            KeyCode::Char(c) => c.to_string(),
+            key => KEY_NAMES
+                .iter()
+                .find(|(_, code)| code == key)
+                .map_or_else(|| format!("{key:?}"), |(name, _)| format!("<{name}>")),
+        })
+        .collect()
+}
+
+/// One key sequence or a list of them in the config file
+#[derive(Deserialize)]
+#[serde(untagged)]
+pub enum KeysConfig {
+    One(String),
+    Many(Vec<String>),
+}
+
+/// The key sequences bound to the actions
+pub struct Keymap {
+    bindings: Vec<(Vec<KeyCode>, Action)>,
+}
+
+impl Default for Keymap {
+    fn default() -> Self {
+        Self::resolve(&HashMap::new()).expect("The default keys are valid")
+    }
+}
+
+impl Keymap {
+    const DEFAULT_KEYS: [(Action, &[&str]); 28] = [
+        (Action::Quit, &["q", "<Backspace>"]),
+        (Action::Up, &["k", "<Up>"]),
+        (Action::Down, &["j", "<Down>"]),
+        (Action::Select, &["<Enter>"]),
+        (Action::Top, &["gg"]),
+        (Action::Refresh, &["r"]),
+        (Action::MarkAllRead, &["M"]),
+        (Action::MarkUnread, &["u"]),
+        (Action::UnreadOnly, &["U"]),
+        (Action::ToggleSource(0), &["1"]),
+        (Action::ToggleSource(1), &["2"]),
+        (Action::ToggleSource(2), &["3"]),
+        (Action::ToggleSource(3), &["4"]),
+        (Action::ToggleSource(4), &["5"]),
+        (Action::ToggleSource(5), &["6"]),
+        (Action::ToggleSource(6), &["7"]),
+        (Action::ToggleSource(7), &["8"]),
+        (Action::ToggleSource(8), &["9"]),
+        (Action::NextTopic, &["t"]),
+        (Action::Search, &["/"]),
+        (Action::NextMatch, &["n"]),
+        (Action::PreviousMatch, &["N"]),
+        (Action::ClearSearch, &["<Esc>"]),
+        (Action::SearchArticles, &["F"]),
+        (Action::ToggleStory, &["o"]),
+        (Action::Save, &["s"]),
+        (Action::OpenSaved, &["S"]),
+        (Action::Help, &["?"]),
+    ];
+
+    /// The default keys, with the actions from the config file rebound
+    pub fn resolve(config: &HashMap<String, KeysConfig>) -> Result<Self, String> {
+        let mut keys = Self::DEFAULT_KEYS
+            .iter()
+            .map(|(action, keys)| (*action, keys.iter().map(|key| key.to_string()).collect()))
+            .collect::<Vec<(Action, Vec<String>)>>();
+        for (name, bound) in config {
+            let action = Action::from_name(name).ok_or_else(|| {
+                let names = Action::NAMES.map(|(name, _)| name);
+                format!(
+                    "unknown action \"{name}\" in keys (the actions are {})",
+                    names.join(", ")
+                )
+            })?;
+            let bound = match bound {
+                KeysConfig::One(key) => vec![key.clone()],
+                KeysConfig::Many(keys) => keys.clone(),
+            };
+            if let Some((_, keys)) = keys.iter_mut().find(|(other, _)| *other == action) {
+                *keys = bound;
+            }
+        }
+
+        let mut bindings = Vec::new();
+        for (action, keys) in keys {
+            for key in keys {
+                let parsed = parse_keys(&key).map_err(|err| format!("keys.{action}: {err}"))?;
+                bindings.push((parsed, action));
+            }
+        }
+        for (index, (keys, action)) in bindings.iter().enumerate() {
+            // A sequence that starts another one would hide it
+            let conflict = bindings[index + 1..].iter().find(|(other_keys, other)| {
+                (other_keys.starts_with(keys) || keys.starts_with(other_keys))
+                    && action.shares_view(*other)
+            });
+            if let Some((other_keys, other)) = conflict {
+                return Err(format!(
+                    "the keys {} of {action} conflict with the keys {} of {other}",
+                    keys_to_string(keys),
+                    keys_to_string(other_keys)
+                ));
+            }
+        }
+        Ok(Self { bindings })
+    }
+
+    /// The actions that can be used in the view with their keys, like ("k, <Up>", Action::Up)
+    pub fn bindings(&self, view: View) -> Vec<(String, Action)> {
+        let mut bindings = Vec::<(String, Action)>::new();
+        for (keys, action) in &self.bindings {
+            if action.control(view).is_none() {
+                continue;
+            }
+            let keys = keys_to_string(keys);
+            match bindings.iter_mut().find(|(_, other)| other == action) {
+                Some((bound, _)) => *bound = format!("{bound}, {keys}"),
+                None => bindings.push((keys, *action)),
+            }
+        }
+        bindings
+    }
+
+    /// The first key sequence of the action in the view, for the hints like "(press r to retry)"
+    pub fn key(&self, action: Action, view: View) -> Option<String> {
+        action.control(view)?;
+        self.bindings
+            .iter()
+            .find(|(_, other)| *other == action)
+            .map(|(keys, _)| keys_to_string(keys))
+    }
 }
 
 pub struct InputBuffer {
-    char_buffer: Vec<char>,
+    key_buffer: Vec<KeyCode>,
 }
 
 impl InputBuffer {
     pub fn new() -> Self {
-        Self {
-            char_buffer: vec![],
-        }
+        Self { key_buffer: vec![] }
     }
 
     pub fn clear(&mut self) {
-        self.char_buffer.clear();
+        self.key_buffer.clear();
     }
 
-    fn map_key(&mut self, c: char, view: View) -> Option<Controls> {
-        self.char_buffer.push(c);
-        match self.char_buffer.as_slice() {
-            ['g'] => return None,
-            _ => {}
-        };
-        let control = match (self.char_buffer.as_slice(), view) {
-            (['k'], View::Feed) => Some(Controls::MoveSelect(Direction::Up)),
-            (['k'], View::Article) => Some(Controls::Scroll(Direction::Up, 1)),
-            (['j'], View::Feed) => Some(Controls::MoveSelect(Direction::Down)),
-            (['j'], View::Article) => Some(Controls::Scroll(Direction::Down, 1)),
-            (['q'], _) => Some(Controls::Quit),
-            (['g', 'g'], _) => Some(Controls::GotoTop),
-            (['r'], View::Feed) => Some(Controls::Refresh),
-            // TODO: Consider adding Controls::GotoBottom
-            _ => None,
-        };
-        if self.char_buffer.len() > 1 {
-            if let (None, Some(c)) = (control, self.char_buffer.pop()) {
-                self.char_buffer.clear();
-                return self.map_key(c, view);
+    fn map_key(&mut self, key: KeyCode, view: View) -> Option<Controls> {
+        self.key_buffer.push(key);
+        let bindings = config::get()
+            .keymap
+            .bindings
+            .iter()
+            .filter(|(_, action)| action.control(view).is_some());
+        let mut is_prefix = false;
+        for (keys, action) in bindings {
+            if *keys == self.key_buffer {
+                self.key_buffer.clear();
+                return action.control(view);
             }
+            is_prefix |= keys.starts_with(&self.key_buffer);
+        }
+        if is_prefix {
+            return None;
+        }
+        // The last key can start a new sequence
+        if self.key_buffer.len() > 1
+            && let Some(key) = self.key_buffer.pop()
+        {
+            self.key_buffer.clear();
+            return self.map_key(key, view);
         }
-        self.char_buffer.clear();
-        return control;
+        self.key_buffer.clear();
+        None
     }
 
     pub fn map(&mut self, event: Event, view: View) -> Option<Controls> {
-        return match event {
+        match event {
             Event::Key(event) => {
                 if event.kind == KeyEventKind::Press {
-                    match (event.code, view) {
-                        (KeyCode::Char(c), view) => {
-                            if event.modifiers.contains(KeyModifiers::CONTROL) && c == 'c' {
-                                Some(Controls::Quit)
-                            } else {
-                                self.map_key(c, view)
-                            }
-                        }
-                        (KeyCode::Enter, View::Feed) => Some(Controls::Select),
-                        (KeyCode::Backspace, _) => Some(Controls::Quit),
-                        (KeyCode::Up, View::Feed) => Some(Controls::MoveSelect(Direction::Up)),
-                        (KeyCode::Up, View::Article) => Some(Controls::Scroll(Direction::Up, 1)),
-                        (KeyCode::Down, View::Feed) => Some(Controls::MoveSelect(Direction::Down)),
-                        (KeyCode::Down, View::Article) => {
-                            Some(Controls::Scroll(Direction::Down, 1))
-                        }
-                        _ => None,
+                    if event.modifiers.contains(KeyModifiers::CONTROL)
+                        && event.code == KeyCode::Char('c')
+                    {
+                        Some(Controls::Quit)
+                    } else {
+                        self.map_key(event.code, view)
                     }
                 } else {
                     None
@@ -112,6 +419,105 @@ impl InputBuffer {
                 _ => None,
             },
             _ => None,
-        };
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn keymap(keys: &[(&str, &[&str])]) -> Result<Keymap, String> {
+        let config = keys
+            .iter()
+            .map(|(action, keys)| {
+                let keys = keys.iter().map(|key| key.to_string()).collect();
+                (action.to_string(), KeysConfig::Many(keys))
+            })
+            .collect();
+        Keymap::resolve(&config)
+    }
+
+    #[test]
+    fn parses_keys() {
+        let cases: [(&str, &[KeyCode]); 6] = [
+            ("gg", &[KeyCode::Char('g'), KeyCode::Char('g')]),
+            ("<Enter>", &[KeyCode::Enter]),
+            ("<pagedown>j", &[KeyCode::PageDown, KeyCode::Char('j')]),
+            ("<Space>", &[KeyCode::Char(' ')]),
+            // Not a key name
+            ("<", &[KeyCode::Char('<')]),
+            ("<>", &[KeyCode::Char('<'), KeyCode::Char('>')]),
+        ];
+        for (keys, expected) in cases {
+            assert_eq!(parse_keys(keys).as_deref(), Ok(expected), "{keys}");
+        }
+        assert!(
+            parse_keys("<Foo>")
+                .unwrap_err()
+                .starts_with("unknown key <Foo>")
+        );
+        assert!(parse_keys("").is_err());
+    }
+
+    #[test]
+    fn writes_keys() {
+        for keys in ["gg", "<Enter>", "<PageDown>j", "<Space>", "<"] {
+            assert_eq!(keys_to_string(&parse_keys(keys).unwrap()), keys);
+        }
+    }
+
+    #[test]
+    fn rebinds_actions() {
+        // The default keys of the rebound actions are free
+        assert!(keymap(&[("up", &["e"]), ("next-match", &["k"])]).is_ok());
+        let keymap = keymap(&[("up", &["e", "<Up>"]), ("next-match", &["h"])]).unwrap();
+        assert_eq!(keymap.key(Action::Up, View::Article).as_deref(), Some("e"));
+        assert_eq!(
+            keymap.key(Action::NextMatch, View::Feed).as_deref(),
+            Some("h")
+        );
+        let bindings = keymap.bindings(View::Feed);
+        assert!(bindings.contains(&("e, <Up>".into(), Action::Up)));
+        assert!(bindings.iter().all(|(keys, _)| keys != "k"));
+    }
+
+    #[test]
+    fn scopes_bindings_to_views() {
+        let keymap = Keymap::default();
+        let actions = keymap
+            .bindings(View::Error)
+            .into_iter()
+            .map(|(_, action)| action);
+        assert!(actions.eq([Action::Quit, Action::Refresh, Action::Help]));
+        assert_eq!(
+            keymap.key(Action::Refresh, View::Error).as_deref(),
+            Some("r")
+        );
+        assert_eq!(keymap.key(Action::Search, View::Error), None);
+        assert_eq!(keymap.key(Action::ToggleStory, View::Article), None);
+        assert_eq!(
+            keymap.key(Action::ToggleStory, View::Feed).as_deref(),
+            Some("o")
+        );
+    }
+
+    #[test]
+    fn rejects_bad_bindings() {
+        let err = keymap(&[("jump", &["x"])]).err().unwrap();
+        assert!(err.starts_with("unknown action \"jump\""), "{err}");
+
+        // g would hide gg
+        let err = keymap(&[("quit", &["g"])]).err().unwrap();
+        assert_eq!(err, "the keys g of quit conflict with the keys gg of top");
+
+        let err = keymap(&[("next-match", &["j"])]).err().unwrap();
+        assert_eq!(
+            err,
+            "the keys j of down conflict with the keys j of next-match"
+        );
+
+        let err = keymap(&[("quit", &["<Foo>"])]).err().unwrap();
+        assert!(err.starts_with("keys.quit: unknown key <Foo>"), "{err}");
     }
 }
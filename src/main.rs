@@ -1,41 +1,61 @@
 mod backend;
+mod cli;
+mod config;
 mod frontend;
 mod input;
 
-use backend::{NewsSite, deserialize_parser, serialize_parser};
+use backend::{
+    ArticleIndex, Client, Filter, NewsSite, ReadArticles, Refresh, Refreshed, SavedArticles,
+    deserialize_parser, serialize_parser,
+};
 use chrono::{DateTime, Local};
+use clap::Parser;
+use cli::{Cli, Command};
 use crossterm::{
     QueueableCommand, cursor,
     event::{self, Event},
     execute,
     terminal::{self, ClearType},
 };
-use frontend::{ComponentKind, Geometry, TextPad};
+use frontend::{ComponentKind, Geometry, Search, TextPad, show_help, transliterate};
 use input::*;
 use log::LevelFilter;
 use log4rs::append::file::FileAppender;
 use log4rs::config::{Appender, Config, Root};
 use log4rs::encode::pattern::PatternEncoder;
-use reqwest::blocking::Client;
 use serde::{Deserialize, Serialize};
 use std::{
     cell::RefCell,
-    collections::VecDeque,
+    collections::{HashMap, HashSet, VecDeque},
     error::Error,
     io::{self, Write, stdout},
     panic, process,
     rc::Rc,
+    sync::{
+        Arc,
+        atomic::{AtomicBool, Ordering},
+        mpsc::{Receiver, Sender, TryRecvError},
+    },
     thread,
     time::{Duration, Instant},
 };
 
-#[derive(Serialize, Deserialize)]
+#[derive(Clone, Serialize, Deserialize)]
 enum Body {
-    Fetched { html: String, lead: String },
-    ToFetch { url: String },
+    Fetched {
+        html: String,
+        lead: String,
+    },
+    ToFetch {
+        url: String,
+    },
+    Saved {
+        id: String,
+        content: Vec<ComponentKind>,
+    },
 }
 
-#[derive(Serialize, Deserialize)]
+#[derive(Clone, Serialize, Deserialize)]
 pub struct FeedItem {
     title: String,
     published: DateTime<Local>,
@@ -44,14 +64,40 @@ pub struct FeedItem {
         serialize_with = "serialize_parser",
         deserialize_with = "deserialize_parser"
     )]
-    parser: Rc<dyn NewsSite>,
+    parser: Arc<dyn NewsSite>,
+    #[serde(default)]
+    categories: Vec<String>,
+    #[serde(default)]
+    read: bool,
+    #[serde(default)]
+    new: bool,
 }
 
 pub struct Feed {
     time: Instant,
     items: VecDeque<FeedItem>,
+    /// Indexes of the items that pass the filter
+    shown: Vec<usize>,
+    /// Index into shown
     selected: usize,
+    filter: Filter,
+    read_articles: ReadArticles,
+    saved_articles: SavedArticles,
+    /// Shared with the saved articles and the search results views
+    index: Rc<RefCell<ArticleIndex>>,
+    /// Snippets of the article text for the search results, by item id
+    snippets: HashMap<String, String>,
+    /// The index of the newest item of the story each item is about
+    clusters: Vec<usize>,
+    /// Ids of the newest items of the stories whose every version is shown
+    expanded: HashSet<String>,
+    /// The saved articles and the search results views aren't refreshed
+    live: bool,
     client: Client,
+    refresh: Option<Refresh>,
+    prefetched: (Sender<String>, Receiver<String>),
+    /// Set while the articles are prefetched, so that the same ones aren't fetched twice at once
+    prefetching: Arc<AtomicBool>,
 }
 
 trait Runnable {
@@ -71,10 +117,10 @@ trait Runnable {
             if event::poll(Duration::ZERO)? {
                 should_run = self.handle_input(event::read()?, &mut qc)?;
             }
-            if let Some(timer) = self.get_timer() {
-                if (Instant::now() - timer).as_secs() >= 60 {
-                    self.run_every_minute(&mut qc)?;
-                }
+            if let Some(timer) = self.get_timer()
+                && (Instant::now() - timer).as_secs() >= config::get().refresh_interval
+            {
+                self.run_every_minute(&mut qc)?;
             }
             thread::sleep(Duration::from_millis(16));
         }
@@ -86,15 +132,36 @@ struct ErrorWindow<'a> {
     msg: String,
     geo: &'a Rc<RefCell<Geometry>>,
     input: InputBuffer,
+    can_retry: bool,
+    should_retry: bool,
 }
 
 impl<'a> ErrorWindow<'a> {
     pub fn build(msg: &str, geo: &'a Rc<RefCell<Geometry>>) -> io::Result<Self> {
+        Self::build_with(format!("(ERROR) {msg}"), geo, false)
+    }
+
+    pub fn build_with_retry(msg: &str, geo: &'a Rc<RefCell<Geometry>>) -> io::Result<Self> {
+        let hint = config::get()
+            .keymap
+            .key(Action::Refresh, View::Error)
+            .map(|key| format!(" (press {key} to retry)"))
+            .unwrap_or_default();
+        Self::build_with(format!("(ERROR) {msg}{hint}"), geo, true)
+    }
+
+    fn build_with(
+        msg: String,
+        geo: &'a Rc<RefCell<Geometry>>,
+        can_retry: bool,
+    ) -> io::Result<Self> {
         let mut stdout = stdout();
         let error_window = Self {
-            msg: format!("(ERROR) {msg}"),
+            msg,
             geo,
             input: InputBuffer::new(),
+            can_retry,
+            should_retry: false,
         };
         error_window.draw(&mut stdout)?;
         stdout.flush()?;
@@ -110,20 +177,127 @@ impl Runnable for ErrorWindow<'_> {
     ) -> io::Result<bool> {
         match self.input.map(event, View::Error) {
             Some(Controls::Quit) => return Ok(false),
+            Some(Controls::Refresh) if self.can_retry => {
+                self.should_retry = true;
+                return Ok(false);
+            }
             Some(Controls::Resize(new_dimens)) => {
                 self.resize(new_dimens);
                 self.draw(&mut qc)?;
                 qc.flush()?;
             }
+            Some(Controls::Help) => {
+                show_help(View::Error, self.geo, &mut qc)?;
+                self.draw(&mut qc)?;
+                qc.flush()?;
+            }
+            _ => {}
+        }
+        Ok(true)
+    }
+}
+
+enum Loaded {
+    Article(Vec<ComponentKind>),
+    Error(String),
+    TimedOut,
+}
+
+struct LoadingWindow<'a> {
+    started: Instant,
+    /// Tells the fetching thread not to cache the article after the loading is cancelled
+    cancelled: Arc<AtomicBool>,
+    receiver: Receiver<Result<Vec<ComponentKind>, String>>,
+    geo: &'a Rc<RefCell<Geometry>>,
+    input: InputBuffer,
+    result: Option<Loaded>,
+}
+
+impl<'a> LoadingWindow<'a> {
+    const TIMEOUT: Duration = Duration::from_secs(15);
+
+    pub fn build(
+        item: &FeedItem,
+        geo: &'a Rc<RefCell<Geometry>>,
+        mut qc: impl QueueableCommand + Write,
+    ) -> io::Result<Self> {
+        geo.borrow_mut().change_view(View::Article);
+        let cancelled = Arc::new(AtomicBool::new(false));
+        let loading_window = Self {
+            started: Instant::now(),
+            receiver: item.fetch_article(cancelled.clone()),
+            cancelled,
+            geo,
+            input: InputBuffer::new(),
+            result: None,
+        };
+        qc.queue(terminal::Clear(ClearType::All))?;
+        loading_window.draw(&mut qc)?;
+        qc.flush()?;
+        Ok(loading_window)
+    }
+}
+
+impl Runnable for LoadingWindow<'_> {
+    fn handle_input(
+        &mut self,
+        event: Event,
+        mut qc: impl QueueableCommand + Write,
+    ) -> io::Result<bool> {
+        match self.input.map(event, View::Loading) {
+            // The fetching thread finishes on its own and its result gets dropped without being cached
+            Some(Controls::Quit) => {
+                self.cancelled.store(true, Ordering::Relaxed);
+                return Ok(false);
+            }
+            Some(Controls::Resize(new_dimens)) => {
+                self.geo.borrow_mut().resize(new_dimens);
+                qc.queue(terminal::Clear(ClearType::All))?;
+                self.draw(&mut qc)?;
+                qc.flush()?;
+            }
+            Some(Controls::Help) => {
+                show_help(View::Loading, self.geo, &mut qc)?;
+                qc.queue(terminal::Clear(ClearType::All))?;
+                self.draw(&mut qc)?;
+                qc.flush()?;
+            }
             _ => {}
         }
         Ok(true)
     }
+
+    fn run(&mut self, mut qc: impl QueueableCommand + Write) -> io::Result<()> {
+        while self.result.is_none() {
+            if event::poll(Duration::ZERO)? && !self.handle_input(event::read()?, &mut qc)? {
+                break;
+            }
+            self.result = match self.receiver.try_recv() {
+                Ok(Ok(article)) => Some(Loaded::Article(article)),
+                Ok(Err(err)) => Some(Loaded::Error(err)),
+                Err(TryRecvError::Disconnected) => {
+                    Some(Loaded::Error("The article fetcher stopped".into()))
+                }
+                Err(TryRecvError::Empty) if self.started.elapsed() >= Self::TIMEOUT => {
+                    Some(Loaded::TimedOut)
+                }
+                Err(TryRecvError::Empty) => None,
+            };
+            self.draw(&mut qc)?;
+            qc.flush()?;
+            thread::sleep(Duration::from_millis(16));
+        }
+        Ok(())
+    }
 }
 
 struct ArticleControler<'a> {
     textpad: TextPad<'a>,
     input: InputBuffer,
+    should_save: bool,
+    search: Search,
+    /// Index of the match the view was last moved to
+    current_match: usize,
 }
 
 impl<'a> ArticleControler<'a> {
@@ -139,6 +313,9 @@ impl<'a> ArticleControler<'a> {
         Ok(Self {
             textpad,
             input: InputBuffer::new(),
+            should_save: false,
+            search: Search::new(),
+            current_match: 0,
         })
     }
 }
@@ -149,6 +326,13 @@ impl Runnable for ArticleControler<'_> {
         event: Event,
         mut qc: impl QueueableCommand + Write,
     ) -> io::Result<bool> {
+        if self.search.is_typing()
+            && let Event::Key(event) = event
+        {
+            self.handle_search_key(&mut qc, event)?;
+            qc.flush()?;
+            return Ok(true);
+        }
         match self.input.map(event, View::Article) {
             Some(Controls::Quit) => return Ok(false),
             Some(Controls::Resize(new_dimens)) => {
@@ -170,6 +354,40 @@ impl Runnable for ArticleControler<'_> {
             Some(Controls::MoveSelect(_)) => {}
             Some(Controls::MouseSelect(..)) => {}
             Some(Controls::Refresh) => {}
+            Some(Controls::MarkAllRead) => {}
+            Some(Controls::MarkUnread) => {}
+            Some(Controls::ToggleUnreadOnly) => {}
+            Some(Controls::ToggleSource(_)) => {}
+            Some(Controls::NextTopic) => {}
+            Some(Controls::Search) => {
+                self.start_search(&mut qc)?;
+                qc.flush()?;
+            }
+            Some(Controls::NextMatch(dir)) => {
+                self.next_match(&mut qc, dir)?;
+                qc.flush()?;
+            }
+            Some(Controls::ClearSearch) => {
+                self.clear_search(&mut qc)?;
+                qc.flush()?;
+            }
+            Some(Controls::SearchArticles) => {}
+            Some(Controls::ToggleStory) => {}
+            Some(Controls::Save) => {
+                self.should_save = true;
+                self.draw_saved(&mut qc)?;
+                qc.flush()?;
+            }
+            Some(Controls::OpenSaved) => {}
+            Some(Controls::Help) => {
+                show_help(View::Article, self.textpad.geo, &mut qc)?;
+                self.textpad.build();
+                self.textpad.draw(&mut qc)?;
+                if self.should_save {
+                    self.draw_saved(&mut qc)?;
+                }
+                qc.flush()?;
+            }
             None => {}
         }
         Ok(true)
@@ -180,6 +398,9 @@ struct FeedControler<'a> {
     feed: Feed,
     textpad: TextPad<'a>,
     input: InputBuffer,
+    search: Search,
+    /// The prompt for searching the text of all fetched articles
+    article_search: Search,
 }
 
 impl<'a> FeedControler<'a> {
@@ -188,13 +409,19 @@ impl<'a> FeedControler<'a> {
         geo: &'a Rc<RefCell<Geometry>>,
         mut qc: impl QueueableCommand + Write,
     ) -> io::Result<Self> {
-        let content = feed.items.iter().map(|i| i.build()).collect::<Vec<_>>();
-        let textpad = TextPad::new(content, geo);
-        let feed_controler = Self {
+        let content = feed.build_shown().collect::<Vec<_>>();
+        let textpad = TextPad::feed(content, geo);
+        if config::get().offline_mode {
+            feed.start_prefetch(feed.items.iter());
+        }
+        let mut feed_controler = Self {
             feed,
             textpad,
             input: InputBuffer::new(),
+            search: Search::new(),
+            article_search: Search::new(),
         };
+        feed_controler.restore_state();
         feed_controler.draw(&mut qc)?;
         qc.flush()?;
         Ok(feed_controler)
@@ -203,8 +430,7 @@ impl<'a> FeedControler<'a> {
 
 impl Runnable for FeedControler<'_> {
     fn run_every_minute(&mut self, mut qc: impl QueueableCommand + Write) -> io::Result<()> {
-        self.refresh(&mut qc)?;
-        self.draw(&mut qc)?;
+        self.start_refresh(&mut qc)?;
         qc.flush()?;
         Ok(())
     }
@@ -218,6 +444,20 @@ impl Runnable for FeedControler<'_> {
         event: Event,
         mut qc: impl QueueableCommand + Write,
     ) -> io::Result<bool> {
+        if self.search.is_typing()
+            && let Event::Key(event) = event
+        {
+            self.handle_search_key(&mut qc, event)?;
+            qc.flush()?;
+            return Ok(true);
+        }
+        if self.article_search.is_typing()
+            && let Event::Key(event) = event
+        {
+            self.handle_article_search_key(&mut qc, event)?;
+            qc.flush()?;
+            return Ok(true);
+        }
         match self.input.map(event, View::Feed) {
             Some(Controls::Quit) => return Ok(false),
             Some(Controls::MoveSelect(dir)) => {
@@ -234,13 +474,12 @@ impl Runnable for FeedControler<'_> {
                 self.select(&mut qc)?;
                 qc.flush()?;
             }
-            Some(Controls::GotoTop) => {
-                if self.feed.selected != 0 {
-                    self.goto_top();
-                    self.draw(&mut qc)?;
-                    qc.flush()?;
-                }
+            Some(Controls::GotoTop) if self.feed.selected != 0 => {
+                self.goto_top();
+                self.draw(&mut qc)?;
+                qc.flush()?;
             }
+            Some(Controls::GotoTop) => {}
             Some(Controls::MouseSelect(column, row)) => {
                 let should_select = self.mouse_select(&mut qc, column, row)?;
                 if should_select {
@@ -249,11 +488,78 @@ impl Runnable for FeedControler<'_> {
                 qc.flush()?;
             }
             Some(Controls::Refresh) => {
-                self.refresh(&mut qc)?;
+                self.start_refresh(&mut qc)?;
+                qc.flush()?;
+            }
+            Some(Controls::MarkAllRead) => {
+                self.mark_all_read();
+                self.draw(&mut qc)?;
+                qc.flush()?;
+            }
+            Some(Controls::MarkUnread) => {
+                self.mark_unread(&mut qc)?;
+                qc.flush()?;
+            }
+            Some(Controls::Save) => {
+                self.toggle_saved(&mut qc)?;
+                qc.flush()?;
+            }
+            Some(Controls::OpenSaved) if self.feed.is_live() => {
+                self.open_saved(&mut qc)?;
+                qc.flush()?;
+            }
+            Some(Controls::OpenSaved) => {}
+            Some(Controls::ToggleUnreadOnly) => {
+                let mut filter = self.feed.filter().clone();
+                filter.unread_only = !filter.unread_only;
+                if self.set_filter(filter) {
+                    self.draw(&mut qc)?;
+                    qc.flush()?;
+                }
+            }
+            Some(Controls::ToggleSource(index)) => {
+                if let Some(site) = backend::Registry::get().enabled().get(index) {
+                    let mut filter = self.feed.filter().clone();
+                    filter.toggle_source(site.to_string());
+                    if self.set_filter(filter) {
+                        self.draw(&mut qc)?;
+                        qc.flush()?;
+                    }
+                }
+            }
+            Some(Controls::NextTopic) => {
+                self.next_topic();
                 self.draw(&mut qc)?;
                 qc.flush()?;
             }
+            Some(Controls::Search) => {
+                self.start_search(&mut qc)?;
+                qc.flush()?;
+            }
+            Some(Controls::NextMatch(dir)) => {
+                self.next_match(&mut qc, dir)?;
+                qc.flush()?;
+            }
+            Some(Controls::ClearSearch) => {
+                self.clear_search(&mut qc)?;
+                qc.flush()?;
+            }
+            Some(Controls::SearchArticles) if self.feed.is_live() => {
+                self.start_article_search(&mut qc)?;
+                qc.flush()?;
+            }
+            Some(Controls::SearchArticles) => {}
+            Some(Controls::ToggleStory) => {
+                self.toggle_story(&mut qc)?;
+                qc.flush()?;
+            }
             Some(Controls::Scroll(..)) => {}
+            Some(Controls::Help) => {
+                show_help(View::Feed, self.textpad.geo, &mut qc)?;
+                self.textpad.build();
+                self.draw(&mut qc)?;
+                qc.flush()?;
+            }
             None => {}
         }
         Ok(true)
@@ -261,17 +567,37 @@ impl Runnable for FeedControler<'_> {
 
     fn run(&mut self, mut qc: impl QueueableCommand + Write) -> io::Result<()> {
         let mut should_run = true;
-        let mut timer = Instant::now();
         while should_run {
             if event::poll(Duration::ZERO)? {
                 should_run = self.handle_input(event::read()?, &mut qc)?;
             }
-            if let Some(new_timer) = self.get_timer() {
-                timer = new_timer;
-            }
-            if (Instant::now() - timer).as_secs() >= 60 {
+            if !config::get().no_refresh
+                && let Some(timer) = self.get_timer()
+                && timer.elapsed().as_secs() >= config::get().refresh_interval
+            {
                 self.run_every_minute(&mut qc)?;
-                timer = Instant::now();
+            }
+            if let Some(refreshed) = self.feed.poll_refresh() {
+                match refreshed {
+                    Refreshed::Added(num_new) => self.add_new_items(&mut qc, num_new)?,
+                    Refreshed::Regrouped => self.rebuild_shown(),
+                }
+                // The articles already in the cache are skipped, so this gets the new ones and
+                // the ones a running prefetch kept from being fetched
+                if config::get().offline_mode {
+                    self.feed.start_prefetch(self.feed.shown());
+                }
+                self.draw(&mut qc)?;
+                qc.flush()?;
+            }
+            let prefetched = self.feed.poll_prefetched();
+            if !prefetched.is_empty() {
+                self.mark_prefetched(&prefetched);
+                self.draw(&mut qc)?;
+                qc.flush()?;
+            } else if self.feed.refreshing_since().is_some() {
+                self.draw_status(&mut qc)?;
+                qc.flush()?;
             }
             thread::sleep(Duration::from_millis(16));
         }
@@ -307,10 +633,9 @@ impl Drop for ScreenState {
     }
 }
 
-fn init_logging() -> Result<(), Box<dyn Error>> {
+fn init_logging(min_log_level: LevelFilter) -> Result<(), Box<dyn Error>> {
     const PATTERN: &str = "{l} - {m}\n";
     const LOGGER_NAME: &str = "logfile";
-    const MIN_LOG_LEVEL: LevelFilter = LevelFilter::Debug;
 
     let timestamp = Local::now().format("%Y-%m-%d_%H-%M-%S");
     let logfile = format!("logs/newsminal_{}.log", timestamp);
@@ -321,21 +646,28 @@ fn init_logging() -> Result<(), Box<dyn Error>> {
 
     let config = Config::builder()
         .appender(Appender::builder().build(LOGGER_NAME, Box::new(logfile)))
-        .build(Root::builder().appender(LOGGER_NAME).build(MIN_LOG_LEVEL))?;
+        .build(Root::builder().appender(LOGGER_NAME).build(min_log_level))?;
 
     log4rs::init_config(config)?;
     Ok(())
 }
 
-// TODO: Add a help command
-// TODO: Add a option url so that newsminal only parses that website
-fn main() -> io::Result<()> {
-    init_logging().unwrap_or_else(|err| {
-        eprintln!("Couldn't init logger: {err}");
-        process::exit(1);
-    });
-    log::info!("Started logging");
+fn start_tui() -> io::Result<(ScreenState, Rc<RefCell<Geometry>>)> {
+    let default_hook = panic::take_hook();
+    panic::set_hook(Box::new(move |info| {
+        let _ = terminal::disable_raw_mode();
+        let _ = execute!(stdout(), terminal::LeaveAlternateScreen);
+        let _ = stdout().flush();
+        default_hook(info)
+    }));
+
+    let screen_state = ScreenState::enable()?;
+    let dimens = terminal::size()?;
+    let geo = Geometry::new(dimens);
+    Ok((screen_state, Rc::new(RefCell::new(geo))))
+}
 
+fn run_feed() -> io::Result<()> {
     let feed = {
         #[cfg(feature = "testdata")]
         {
@@ -355,19 +687,87 @@ fn main() -> io::Result<()> {
         }
     };
 
-    let default_hook = panic::take_hook();
-    panic::set_hook(Box::new(move |info| {
-        let _ = terminal::disable_raw_mode();
-        let _ = execute!(stdout(), terminal::LeaveAlternateScreen);
-        let _ = stdout().flush();
-        default_hook(info)
-    }));
-
-    let _screen_state = ScreenState::enable()?;
-    let dimens = terminal::size()?;
-    let geo = Geometry::new(dimens);
-    let geo = Rc::new(RefCell::new(geo));
+    let (_screen_state, geo) = start_tui()?;
     let mut stdout = stdout();
     let mut feed_controler = FeedControler::build(feed, &geo, &mut stdout)?;
-    feed_controler.run(&mut stdout)
+    feed_controler.run(&mut stdout)?;
+
+    #[cfg(not(feature = "testdata"))]
+    feed_controler
+        .feed
+        .save()
+        .unwrap_or_else(|err| log::error!("Couldn't save the feed: {err}"));
+    Ok(())
+}
+
+fn read(url: &str) -> io::Result<()> {
+    let mut item = FeedItem::from_url(url).unwrap_or_else(|err| {
+        eprintln!("Couldn't open {url}: {err}");
+        process::exit(1);
+    });
+
+    let (_screen_state, geo) = start_tui()?;
+    let mut stdout = stdout();
+    let Some(article) = LoadingWindow::load(&item, &geo, &mut stdout)? else {
+        return Ok(());
+    };
+    let mut article_controler = ArticleControler::build(article.clone(), &geo, &mut stdout)?;
+    article_controler.run(&mut stdout)?;
+    if article_controler.should_save {
+        if let Some(ComponentKind::Title(title)) = article.first() {
+            item.title = format!("[{}] {title}", item.parser);
+        }
+        SavedArticles::load().add(&item, article);
+    }
+    Ok(())
+}
+
+fn list() -> io::Result<()> {
+    let feed = if config::get().no_refresh {
+        Feed::new()
+    } else {
+        Feed::fetch()
+    };
+    let feed = feed.unwrap_or_else(|err| {
+        eprintln!("Couldn't get feed: {err}");
+        process::exit(1);
+    });
+    let mut stdout = stdout().lock();
+    for item in feed.shown() {
+        writeln!(
+            stdout,
+            "{} {}",
+            item.published.format("%d.%m. %H:%M"),
+            transliterate(&item.title, config::get().script)
+        )?;
+    }
+    Ok(())
+}
+
+fn main() -> io::Result<()> {
+    let cli = Cli::parse();
+    init_logging(cli.log_level).unwrap_or_else(|err| {
+        eprintln!("Couldn't init logger: {err}");
+        process::exit(1);
+    });
+    log::info!("Started logging");
+
+    let mut config = config::Config::load(cli.config).unwrap_or_else(|err| {
+        eprintln!("Couldn't load config: {err}");
+        process::exit(1);
+    });
+    if let Some(sources) = cli.sources {
+        config.enable_only(sources).unwrap_or_else(|err| {
+            eprintln!("Invalid --sources: {err}");
+            process::exit(1);
+        });
+    }
+    config.no_refresh = cli.no_refresh;
+    config::init(config);
+
+    match cli.command {
+        None => run_feed(),
+        Some(Command::Read { url }) => read(&url),
+        Some(Command::List) => list(),
+    }
 }